@@ -1,14 +1,22 @@
 pub mod component_cache;
 pub mod event_cache;
+pub mod input_queue;
 
 use std::marker::PhantomData;
 use serde::{Serialize, de::DeserializeOwned};
 use bevy::prelude::*;
 use bevy_replicon::prelude::*;
-use crate::{ClientBootSet, NetworkEvent, ServerBootSet};
+use crate::{
+    ClientBootSet, 
+    ServerBootSet, 
+    NetworkEvent, 
+    RedundantEvents,
+    RedundantEventBuffer
+};
 
 pub use component_cache::*;
 pub use event_cache::*;
+pub use input_queue::*;
 
 pub struct ClientEventCachePlugin<E: NetworkEvent>(PhantomData<E>);
 
@@ -36,6 +44,43 @@ impl<E: NetworkEvent> Plugin for ClientEventCachePlugin<E> {
     }
 }
 
+pub struct RedundantEventPlugin<E: NetworkEvent>{
+    pub redundancy: usize,
+    phantom: PhantomData<E>
+}
+
+impl<E: NetworkEvent> RedundantEventPlugin<E> {
+    #[inline]
+    pub fn new(redundancy: usize) -> Self {
+        Self { 
+            redundancy, 
+            phantom: PhantomData::<E> 
+        }
+    }
+}
+
+impl<E: NetworkEvent> Plugin for RedundantEventPlugin<E> {
+    fn build(&self, app: &mut App) {
+        if app.world().contains_resource::<RepliconServer>() {
+            app.add_event::<FromClient<E>>()
+            .add_systems(PreUpdate, 
+                server_unpack_redundant_events::<E>
+                .after(ServerSet::Receive)
+                .before(ServerBootSet::UnboxEvent)
+            );
+        } else if app.world().contains_resource::<RepliconClient>() {
+            app.add_event::<E>()
+            .insert_resource(RedundantEventBuffer::<E>::new(self.redundancy))
+            .add_systems(PostUpdate, 
+                client_send_redundant_events::<E>
+                .before(ClientSet::Send)
+            );
+        } else {
+            panic!("could not find replicon server nor client");
+        }
+    }
+}
+
 pub struct ComponentCachePlugin<C>(PhantomData<C>)
 where C: Component + Serialize + DeserializeOwned + Clone;
 
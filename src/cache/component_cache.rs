@@ -1,12 +1,10 @@
 use anyhow::bail;
-use bevy::{
-    prelude::*,
-    utils::SystemTime
-};
+use bevy::prelude::*;
 use bevy_replicon::{
     client::confirm_history, 
     server::server_tick::ServerTick 
 };
+use crate::core::NetworkClock;
 
 #[derive(Clone)]
 pub struct ComponentSnapshot<C: Component + Clone> {
@@ -59,10 +57,10 @@ impl<C: Component + Clone> ComponentCache<C> {
     }
 
     #[inline]
-    pub fn with_init(init: C, tick: u32, cache_size: usize) 
+    pub fn with_init(init: C, tick: u32, timestamp: f64, cache_size: usize) 
     -> anyhow::Result::<Self> {
         let mut cache = Self::with_capacity(cache_size);
-        match cache.insert(init, tick) {
+        match cache.insert(init, tick, timestamp) {
             Ok(()) => Ok(cache),
             Err(e) => Err(e) 
         }
@@ -106,7 +104,7 @@ impl<C: Component + Clone> ComponentCache<C> {
     }
 
     #[inline]
-    pub fn elapsed(&self) -> anyhow::Result<f64> {
+    pub fn elapsed(&self, now: f64) -> anyhow::Result<f64> {
         if self.frontier_len() == 0 {
             bail!("frontier is empty");
         }
@@ -115,9 +113,6 @@ impl<C: Component + Clone> ComponentCache<C> {
         let back = self.frontier_back()
         .unwrap();
 
-        let now = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)?
-        .as_secs_f64();
         let elapsed = now - back.timestamp();
         if elapsed < 0.0 {
             bail!("back is future");
@@ -127,17 +122,63 @@ impl<C: Component + Clone> ComponentCache<C> {
     }
 
     #[inline]
-    pub fn elapsed_per_network_tick(&self, network_tick_delta: f64)
+    pub fn elapsed_per_network_tick(&self, now: f64, network_tick_delta: f64)
     -> anyhow::Result<f32> {
         if network_tick_delta == 0.0 {
             bail!("invalid network tick delta");
         }
         
-        let elapsed = self.elapsed()?;
+        let elapsed = self.elapsed(now)?;
         let per = (elapsed / network_tick_delta) as f32;
         Ok(per)
     }
 
+    /// returns pair around fractional tick in frontier and progress between them,
+    /// clamped to first or last snapshot out of buffered range
+    pub fn sample_at_tick(&self, tick: f64) 
+    -> Option<(&ComponentSnapshot<C>, &ComponentSnapshot<C>, f32)> {
+        let first = self.frontier.first()?;
+        if tick <= first.tick() as f64 {
+            return Some((first, first, 0.0));
+        }
+
+        for pair in self.frontier.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            if tick >= to.tick() as f64 {
+                continue;
+            }
+
+            let span = to.tick().saturating_sub(from.tick()) as f64;
+            let per = if span > 0.0 {
+                ((tick - from.tick() as f64) / span) as f32
+            } else {
+                1.0
+            };
+            return Some((from, to, per));
+        }
+
+        let last = self.frontier.last()?;
+        Some((last, last, 1.0))
+    }
+
+    /// count of frontier snapshots that are not needed to sample tick anymore
+    #[inline]
+    pub fn consumed_before_tick(&self, tick: f64) -> usize {
+        self.frontier.iter()
+        .skip(1)
+        .take_while(|s| s.tick() as f64 <= tick)
+        .count()
+    }
+
+    /// latest snapshot at or before tick in both cache and frontier
+    #[inline]
+    pub fn snapshot_at_tick(&self, tick: u32) -> Option<&ComponentSnapshot<C>> {
+        self.cache.iter()
+        .chain(self.frontier.iter())
+        .filter(|s| s.tick <= tick)
+        .last()
+    }
+
     #[inline]
     pub fn find_at_tick(&self, tick: u32) -> Option<&ComponentSnapshot<C>> {
         self.cache.iter().rfind(|s| s.tick <= tick)
@@ -173,7 +214,7 @@ impl<C: Component + Clone> ComponentCache<C> {
         &self.cache
     }
 
-    pub fn insert(&mut self, component: C, tick: u32) 
+    pub fn insert(&mut self, component: C, tick: u32, timestamp: f64) 
     -> anyhow::Result<()> {
         let frontier_len = self.frontier_len();
         
@@ -193,10 +234,6 @@ impl<C: Component + Clone> ComponentCache<C> {
             );
         }
 
-        let timestamp = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)?
-        .as_secs_f64();
-
         if let Some(frontier_snap) = self.frontier_front() {
             if tick < frontier_snap.tick {
                 bail!(
@@ -260,7 +297,8 @@ impl<C: Component + Clone> ComponentCache<C> {
         }
 
         if self.cache_len() + n > self.cache_size {
-            self.cache.drain(..n);
+            let overflow = self.cache_len() + n - self.cache_size;
+            self.cache.drain(..overflow);
         }
 
         let drain = self.frontier.drain(..n);
@@ -289,7 +327,8 @@ impl<C: Component + Clone> ComponentCache<C> {
         }
 
         if self.cache_len() + frontier_len > self.cache_size {
-            self.cache.drain(..frontier_len);
+            let overflow = self.cache_len() + frontier_len - self.cache_size;
+            self.cache.drain(..overflow);
         }
 
         let drain = self.frontier.drain(..);
@@ -300,17 +339,19 @@ impl<C: Component + Clone> ComponentCache<C> {
     }
 }
 
-pub(super) fn server_populate_component_cache<C>(
+pub(crate) fn server_populate_component_cache<C>(
     mut query: Query<
         (&C, &mut ComponentCache<C>), 
         Changed<C>
     >,
-    server_tick: Res<ServerTick>
+    server_tick: Res<ServerTick>,
+    clock: Res<NetworkClock>
 )
 where C: Component + Clone { 
     let tick = server_tick.get();
+    let now = clock.now();
     for (c, mut cache) in query.iter_mut() {
-        match cache.insert(c.clone(), tick) {
+        match cache.insert(c.clone(), tick, now) {
             Ok(()) => trace!(
                 "inserted component snapshot: frontier len: {}, cache len: {}",
                 cache.frontier_len(),
@@ -329,13 +370,15 @@ pub(super) fn client_populate_component_cache<C>(
     ), 
         Changed<C>
     >,
+    clock: Res<NetworkClock>
 )
 where C: Component + Clone {
+    let now = clock.now();
     for (c, mut cache, confirmed_tick) in query.iter_mut() {
         // this as latest replication should be latest tick for this client
         // because this is changed at this tick
         let tick = confirmed_tick.last_tick().get();
-        match cache.insert(c.clone(), tick) {
+        match cache.insert(c.clone(), tick, now) {
             Ok(()) => trace!(
                 "inserted component snapshot frontier len: {}, cache len: {}",
                 cache.frontier_len(),
@@ -345,3 +388,80 @@ where C: Component + Clone {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{Clock, ManualClock};
+    use super::*;
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct TestValue(f32);
+
+    fn filled_cache(clock: &ManualClock) -> ComponentCache<TestValue> {
+        let mut cache = ComponentCache::with_capacity(8);
+        for (tick, v) in [(10, 1.0), (12, 2.0), (14, 3.0)] {
+            clock.advance(0.1);
+            cache.insert(TestValue(v), tick, clock.now())
+            .unwrap();
+        }
+        cache
+    }
+
+    #[test]
+    fn sample_at_tick_between_snapshots() {
+        let clock = ManualClock::default();
+        let cache = filled_cache(&clock);
+
+        let (from, to, per) = cache.sample_at_tick(11.0).unwrap();
+        assert_eq!((from.tick(), to.tick()), (10, 12));
+        assert!((per - 0.5).abs() < 1e-6);
+
+        let (from, to, per) = cache.sample_at_tick(13.5).unwrap();
+        assert_eq!((from.tick(), to.tick()), (12, 14));
+        assert!((per - 0.75).abs() < 1e-6);
+
+        assert_eq!(cache.consumed_before_tick(12.5), 1);
+    }
+
+    #[test]
+    fn sample_at_tick_clamps_out_of_range() {
+        let clock = ManualClock::default();
+        let cache = filled_cache(&clock);
+
+        let (from, to, per) = cache.sample_at_tick(3.0).unwrap();
+        assert_eq!((from.tick(), to.tick(), per), (10, 10, 0.0));
+
+        let (from, to, per) = cache.sample_at_tick(20.0).unwrap();
+        assert_eq!((from.tick(), to.tick(), per), (14, 14, 1.0));
+
+        let empty = ComponentCache::<TestValue>::with_capacity(8);
+        assert!(empty.sample_at_tick(10.0).is_none());
+    }
+
+    #[test]
+    fn find_at_tick_searches_cache() {
+        let clock = ManualClock::default();
+        let mut cache = filled_cache(&clock);
+        assert!(cache.find_at_tick(14).is_none());
+
+        cache.cache();
+        assert!(cache.find_at_tick(9).is_none());
+        assert_eq!(cache.find_at_tick(10).unwrap().component(), &TestValue(1.0));
+        assert_eq!(cache.find_at_tick(13).unwrap().component(), &TestValue(2.0));
+        assert_eq!(cache.find_at_tick(99).unwrap().tick(), 14);
+    }
+
+    #[test]
+    fn elapsed_follows_manual_clock() {
+        let clock = ManualClock::default();
+        let cache = filled_cache(&clock);
+
+        clock.advance(0.25);
+        let elapsed = cache.elapsed(clock.now()).unwrap();
+        assert!((elapsed - 0.25).abs() < 1e-9);
+
+        let per = cache.elapsed_per_network_tick(clock.now(), 0.1).unwrap();
+        assert!((per - 2.5).abs() < 1e-5);
+        assert!(cache.elapsed(0.0).is_err());
+    }
+}
@@ -1,12 +1,15 @@
 use anyhow::bail;
-use bevy::{
-    utils::SystemTime,
-    prelude::*
-};
+use bevy::prelude::*;
 use bevy_replicon::prelude::*;
 use crate::{
     Owning, 
-    core::{NetworkEntity, NetworkEvent}
+    core::{
+        NetworkEntity, 
+        NetworkEvent, 
+        NetworkClock,
+        RedundantEvents, 
+        RedundantEventBuffer
+    }
 };
 
 #[derive(Clone)]
@@ -94,6 +97,11 @@ impl<E: NetworkEvent> EventCache<E> {
         self.frontier_index
     }
 
+    #[inline]
+    pub fn last_processed_index(&self) -> Option<usize> {
+        self.frontier_index.checked_sub(1)
+    }
+
     #[inline]
     pub fn frontier_ref(&self) -> &Vec<EventSnapshot<E>> {
         &self.frontier
@@ -109,7 +117,14 @@ impl<E: NetworkEvent> EventCache<E> {
         self.frontier.push(snap);
     }
 
-    pub fn insert(&mut self, event: E)
+    /// event is already processed or waiting in frontier
+    #[inline]
+    pub fn is_duplicate(&self, index: usize) -> bool {
+        index < self.frontier_index
+        || self.frontier.iter().any(|s| s.index() == index)
+    }
+
+    pub fn insert(&mut self, event: E, received_timestamp: f64)
     -> anyhow::Result<()> {
         let frontier_len = self.frontier_len();
 
@@ -129,10 +144,6 @@ impl<E: NetworkEvent> EventCache<E> {
             );
         }
 
-        let received_timestamp = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)?
-        .as_secs_f64();
-
         if let Some(frontier_snap) = self.frontier_front() {
             if event.tick() < frontier_snap.sent_tick() {
                 bail!(
@@ -159,6 +170,15 @@ impl<E: NetworkEvent> EventCache<E> {
         Ok(())
     }
 
+    /// marks next index as consumed without event,
+    /// the event arriving later is rejected as too old
+    #[inline]
+    pub fn skip_index(&mut self) -> usize {
+        let idx = self.frontier_index;
+        self.frontier_index += 1;
+        idx
+    }
+
     #[inline]
     pub fn sort_frontier_by_index(&mut self) {
         if self.frontier_len() == 0 {
@@ -169,6 +189,46 @@ impl<E: NetworkEvent> EventCache<E> {
         .sort_unstable_by_key(|s| s.index());
     }
 
+    pub fn rewind(&mut self, last_index: usize) -> usize {
+        // acknowledged events are already simulated on server
+        self.frontier.retain(|s| s.index() > last_index);
+
+        let (mut replay, acknowledged): (Vec<_>, Vec<_>) = self.cache
+        .drain(..)
+        .partition(|s| s.index() > last_index);
+        self.cache = acknowledged;
+
+        let replay_len = replay.len();
+        replay.append(&mut self.frontier);
+        self.frontier = replay;
+        self.sort_frontier_by_index();
+        self.frontier_index = last_index + 1;
+
+        replay_len
+    }
+
+    /// drops waiting events whose index is already consumed or skipped
+    #[inline]
+    pub fn discard_stale_frontier(&mut self) -> usize {
+        let len = self.frontier.len();
+        let frontier_index = self.frontier_index;
+        self.frontier.retain(|s| s.index() >= frontier_index);
+        len - self.frontier.len()
+    }
+
+    #[inline]
+    pub fn first_after(&self, index: usize) -> Option<&EventSnapshot<E>> {
+        self.cache.iter()
+        .chain(self.frontier.iter())
+        .filter(|s| s.index() > index)
+        .min_by_key(|s| s.index())
+    }
+
+    #[inline]
+    pub fn discard_acknowledged(&mut self, last_index: usize) {
+        self.cache.retain(|s| s.index() > last_index);
+    }
+
     pub fn cache_n(&mut self, n: usize) {
         if n == 0 {
             return;
@@ -179,6 +239,13 @@ impl<E: NetworkEvent> EventCache<E> {
             return;
         }
 
+        // frontier is longer than or equal n
+        let latest_idx = self.frontier[..n].iter()
+        .map(|s| s.index())
+        .max()
+        .unwrap();
+        self.frontier_index = self.frontier_index.max(latest_idx + 1);
+
         if self.cache_size == 0 {
             self.frontier.drain(..n);
             return;
@@ -197,7 +264,8 @@ impl<E: NetworkEvent> EventCache<E> {
         }
 
         if self.cache_len() + n > self.cache_size {
-            self.cache.drain(..n);
+            let overflow = self.cache_len() + n - self.cache_size;
+            self.cache.drain(..overflow);
         }
 
         let drain = self.frontier.drain(..n);
@@ -213,8 +281,14 @@ impl<E: NetworkEvent> EventCache<E> {
             return;
         } 
         
+        // frontier is not empty
+        let latest_idx = self.frontier_back()
+        .unwrap()
+        .index(); 
+        
         if self.cache_size == 0 {
             self.frontier.clear();
+            self.frontier_index = self.frontier_index.max(latest_idx + 1);
             return;
         } 
 
@@ -225,16 +299,13 @@ impl<E: NetworkEvent> EventCache<E> {
         }
         
         if self.cache_len() + frontier_len > self.cache_size {
-            self.cache.drain(..frontier_len);
+            let overflow = self.cache_len() + frontier_len - self.cache_size;
+            self.cache.drain(..overflow);
         }
 
-        // frontier is not empty
-        let latest_idx = self.frontier_back()
-        .unwrap()
-        .index(); 
         let drain = self.frontier.drain(..);
         self.cache.append(&mut drain.collect());
-        self.frontier_index = latest_idx + 1;
+        self.frontier_index = self.frontier_index.max(latest_idx + 1);
 
         debug_assert!(self.frontier_len() == 0);
         debug_assert!(self.cache_len() <= self.cache_size);
@@ -243,8 +314,10 @@ impl<E: NetworkEvent> EventCache<E> {
 
 pub(super) fn server_populate_client_event_cache<E: NetworkEvent>(
     mut query: Query<(&NetworkEntity, &mut EventCache<E>)>,
-    mut events: EventReader<FromClient<E>>
+    mut events: EventReader<FromClient<E>>,
+    clock: Res<NetworkClock>
 ) {
+    let now = clock.now();
     for FromClient { client_id, event } in events.read() {
         if let Err(e) = event.validate() {
             warn!("validation fail: {e}");
@@ -256,7 +329,15 @@ pub(super) fn server_populate_client_event_cache<E: NetworkEvent>(
                 continue;
             }
 
-            match cache.insert(event.clone()) {
+            let mut event = event.clone();
+            event.unwrap_index(cache.frontier_index());
+
+            if cache.is_duplicate(event.index()) {
+                trace!("discarding duplicated event: index: {}", event.index());
+                continue;
+            }
+
+            match cache.insert(event, now) {
                 Ok(()) => trace!(
                     "inserted event snapshot: frontier index: {} frontier len: {}, cache len: {}",
                     cache.frontier_index(),
@@ -272,7 +353,9 @@ pub(super) fn server_populate_client_event_cache<E: NetworkEvent>(
 pub(super) fn client_populate_client_event_cache<E: NetworkEvent>(
     mut query: Query<&mut EventCache<E>, With<Owning>>,
     mut events: EventReader<E>,
+    clock: Res<NetworkClock>
 ) {
+    let now = clock.now();
     for event in events.read() {
         if let Err(e) = event.validate() {
             warn!("validation fail: {e}");
@@ -280,7 +363,7 @@ pub(super) fn client_populate_client_event_cache<E: NetworkEvent>(
         }
 
         for mut cache in query.iter_mut() {
-            match cache.insert(event.clone()) {
+            match cache.insert(event.clone(), now) {
                 Ok(()) => trace!(
                     "inserted event snapshot: frontier index: {} frontier len: {}, cache len: {}",
                     cache.frontier_index(),
@@ -292,3 +375,139 @@ pub(super) fn client_populate_client_event_cache<E: NetworkEvent>(
         }
     }
 }
+
+pub(super) fn server_unpack_redundant_events<E: NetworkEvent>(
+    mut redundants: EventReader<FromClient<RedundantEvents<E>>>,
+    mut events: EventWriter<FromClient<E>>
+) {
+    for FromClient { client_id, event } in redundants.read() {
+        for e in event.events.iter() {
+            events.send(FromClient { 
+                client_id: *client_id, 
+                event: e.clone() 
+            });
+        }
+    }
+}
+
+pub(super) fn client_send_redundant_events<E: NetworkEvent>(
+    mut events: EventReader<E>,
+    mut buffer: ResMut<RedundantEventBuffer<E>>,
+    mut redundants: EventWriter<RedundantEvents<E>>
+) {
+    let mut has_new = false;
+    for e in events.read() {
+        buffer.push(e.clone());
+        has_new = true;
+    }
+
+    if has_new {
+        redundants.send(buffer.to_redundant_events());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Serialize, Deserialize};
+    use crate::core::{Clock, ManualClock};
+    use super::*;
+
+    #[derive(Event, Serialize, Deserialize, Clone)]
+    struct TestEvent {
+        index: usize,
+        tick: u32
+    }
+
+    impl NetworkEvent for TestEvent {
+        fn index(&self) -> usize {
+            self.index
+        }
+
+        fn tick(&self) -> u32 {
+            self.tick
+        }
+
+        fn validate(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn filled_cache(clock: &ManualClock, len: usize) -> EventCache<TestEvent> {
+        let mut cache = EventCache::with_capacity(16);
+        for i in 0..len {
+            clock.advance(0.1);
+            cache.insert(TestEvent{ index: i, tick: i as u32 }, clock.now())
+            .unwrap();
+        }
+        cache
+    }
+
+    fn indices(snaps: &[EventSnapshot<TestEvent>]) -> Vec<usize> {
+        snaps.iter().map(|s| s.index()).collect()
+    }
+
+    #[test]
+    fn insert_rejects_old_tick_and_index() {
+        let clock = ManualClock::default();
+        let mut cache = filled_cache(&clock, 3);
+        assert_eq!(cache.frontier_len(), 3);
+        assert_eq!(cache.frontier_ref()[1].received_timestamp(), 0.2);
+
+        clock.advance(0.1);
+        assert!(cache.insert(TestEvent{ index: 3, tick: 0 }, clock.now()).is_ok());
+        cache.cache();
+
+        clock.advance(0.1);
+        assert!(cache.insert(TestEvent{ index: 5, tick: 5 }, clock.now()).is_ok());
+        assert!(cache.insert(TestEvent{ index: 6, tick: 4 }, clock.now()).is_err());
+        assert!(cache.insert(TestEvent{ index: 2, tick: 6 }, clock.now()).is_err());
+    }
+
+    #[test]
+    fn is_duplicate_covers_processed_and_waiting() {
+        let clock = ManualClock::default();
+        let mut cache = filled_cache(&clock, 3);
+        cache.cache_n(2);
+
+        assert_eq!(cache.frontier_index(), 2);
+        assert!(cache.is_duplicate(0));
+        assert!(cache.is_duplicate(1));
+        assert!(cache.is_duplicate(2));
+        assert!(!cache.is_duplicate(3));
+
+        // skipped index is rejected when it arrives late
+        cache.cache();
+        assert_eq!(cache.skip_index(), 3);
+        assert!(cache.is_duplicate(3));
+        assert!(!cache.is_duplicate(4));
+    }
+
+    #[test]
+    fn rewind_moves_unacknowledged_back_to_frontier() {
+        let clock = ManualClock::default();
+        let mut cache = filled_cache(&clock, 5);
+        cache.cache_n(4);
+
+        let replay = cache.rewind(1);
+        assert_eq!(replay, 2);
+        assert_eq!(cache.frontier_index(), 2);
+        assert_eq!(indices(cache.cache_ref()), vec![0, 1]);
+        assert_eq!(indices(cache.frontier_ref()), vec![2, 3, 4]);
+        assert_eq!(cache.first_after(1).map(|s| s.index()), Some(2));
+    }
+
+    #[test]
+    fn discard_acknowledged_keeps_frontier() {
+        let clock = ManualClock::default();
+        let mut cache = filled_cache(&clock, 5);
+        cache.cache_n(3);
+
+        cache.discard_acknowledged(1);
+        assert_eq!(indices(cache.cache_ref()), vec![2]);
+        assert_eq!(indices(cache.frontier_ref()), vec![3, 4]);
+
+        cache.discard_acknowledged(4);
+        assert_eq!(cache.cache_len(), 0);
+        assert_eq!(cache.frontier_len(), 2);
+    }
+}
@@ -0,0 +1,275 @@
+use std::marker::PhantomData;
+use serde::{Serialize, Deserialize};
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    server::server_tick::ServerTick
+};
+use crate::core::{NetworkEntity, NetworkMovement};
+use super::EventCache;
+
+#[derive(Default, Clone, Copy)]
+pub enum MissingInput {
+    /// simulate last consumed input again
+    #[default]
+    RepeatLast,
+    /// simulate default input
+    Zero
+}
+
+#[derive(Resource, Clone)]
+pub struct InputQueueConfig {
+    /// inputs buffered before consuming starts
+    pub target_depth: usize,
+    /// buffered inputs over this are dropped to target depth
+    pub max_depth: usize,
+    pub missing_input: MissingInput,
+    /// feedback is sent to client every this fixed ticks
+    pub feedback_interval: u32
+}
+
+#[derive(Component)]
+pub struct InputQueue<E: NetworkMovement> {
+    last: Option<E>,
+    is_buffering: bool,
+    missed_count: u32,
+    dropped_count: u32,
+    ticks: u32
+}
+
+impl<E: NetworkMovement> Default for InputQueue<E> {
+    #[inline]
+    fn default() -> Self {
+        Self { 
+            last: None, 
+            is_buffering: true, 
+            missed_count: 0, 
+            dropped_count: 0,
+            ticks: 0 
+        }
+    }
+}
+
+impl<E: NetworkMovement> InputQueue<E> {
+    #[inline]
+    pub fn is_buffering(&self) -> bool {
+        self.is_buffering
+    }
+
+    #[inline]
+    pub fn missed_count(&self) -> u32 {
+        self.missed_count
+    }
+
+    #[inline]
+    pub fn dropped_count(&self) -> u32 {
+        self.dropped_count
+    }
+
+    #[inline]
+    pub fn last(&self) -> Option<&E> {
+        self.last.as_ref()
+    }
+
+    #[inline]
+    pub(crate) fn take_feedback(&mut self, interval: u32) -> Option<(u32, u32)> {
+        if self.ticks < interval {
+            return None;
+        }
+
+        let counts = (self.missed_count, self.dropped_count);
+        self.ticks = 0;
+        self.missed_count = 0;
+        self.dropped_count = 0;
+        Some(counts)
+    }
+
+    pub fn pop(&mut self, cache: &mut EventCache<E>, config: &InputQueueConfig) 
+    -> Option<E> {
+        self.ticks = self.ticks.saturating_add(1);
+        cache.sort_frontier_by_index();
+        let stale = cache.discard_stale_frontier();
+        self.dropped_count = self.dropped_count.saturating_add(stale as u32);
+        
+        let depth = cache.frontier_len();
+        if self.is_buffering {
+            if depth < config.target_depth {
+                // keep simulating while buffer refills
+                return self.substitute(cache, config);
+            }
+
+            self.is_buffering = false;
+        }
+
+        if depth > config.max_depth {
+            // too late to simulate all of them in time
+            let drop = depth - config.target_depth;
+            cache.cache_n(drop);
+            self.dropped_count = self.dropped_count.saturating_add(drop as u32);
+            warn!("dropped {drop} inputs, buffer depth: {depth}");
+        }
+
+        if let Some(snap) = cache.frontier_front() {
+            let input = snap.event()
+            .clone();
+            cache.cache_n(1);
+            self.last = Some(input.clone());
+            return Some(input);
+        }
+
+        // refill to target depth before consuming again
+        self.is_buffering = true;
+        self.substitute(cache, config)
+    }
+
+    fn substitute(&mut self, cache: &mut EventCache<E>, config: &InputQueueConfig) 
+    -> Option<E> {
+        // nothing is consumed yet, there is no index to substitute
+        self.last.as_ref()?;
+
+        self.missed_count = self.missed_count.saturating_add(1);
+        // substituted input takes the index of missing one,
+        // so it is not simulated again when it arrives late
+        let idx = cache.skip_index();
+        // real one waiting for skipped index is simulated by the substitute
+        let stale = cache.discard_stale_frontier();
+        self.dropped_count = self.dropped_count.saturating_add(stale as u32);
+        debug!("input: {idx} is missing, buffer depth: {}", cache.frontier_len());
+        match config.missing_input {
+            MissingInput::RepeatLast => self.last.clone(),
+            MissingInput::Zero => Some(E::default())
+        }
+    }
+}
+
+pub(crate) fn insert_input_queue_system<E: NetworkMovement>(
+    mut commands: Commands,
+    query: Query<
+        Entity, (
+        With<EventCache<E>>,
+        Without<InputQueue<E>>
+    )>
+) {
+    for e in query.iter() {
+        commands.entity(e)
+        .insert(InputQueue::<E>::default());
+    }
+}
+
+#[derive(Event, Serialize, Deserialize)]
+pub struct InputQueueFeedback<E: NetworkMovement> {
+    pub depth: u32,
+    pub target_depth: u32,
+    pub missed: u32,
+    pub dropped: u32,
+    pub tick: u32,
+    phantom: PhantomData<E>
+}
+
+impl<E: NetworkMovement> InputQueueFeedback<E> {
+    #[inline]
+    pub fn new(
+        depth: usize, 
+        target_depth: usize, 
+        missed: u32, 
+        dropped: u32, 
+        tick: u32
+    ) -> Self {
+        Self { 
+            depth: depth as u32, 
+            target_depth: target_depth as u32, 
+            missed, 
+            dropped, 
+            tick, 
+            phantom: PhantomData::<E> 
+        }
+    }
+}
+
+pub type SendInputQueueFeedback<E> = ToClients<InputQueueFeedback<E>>;
+
+#[derive(Resource, Clone)]
+pub struct TimeDilationConfig {
+    /// fixed timestep is scaled within 1.0 -/+ this
+    pub max_dilation: f64,
+    /// dilation per one input of depth error
+    pub gain: f64
+}
+
+#[derive(Resource, Default)]
+pub struct TimeDilation {
+    nominal_timestep: Option<f64>,
+    dilation: f64
+}
+
+impl TimeDilation {
+    /// simulation should always step with this, not with dilated one
+    #[inline]
+    pub fn nominal_timestep(&self) -> Option<f64> {
+        self.nominal_timestep
+    }
+
+    #[inline]
+    pub fn dilation(&self) -> f64 {
+        self.dilation
+    }
+}
+
+pub(crate) fn input_queue_feedback_system<E: NetworkMovement>(
+    mut query: Query<(
+        &NetworkEntity,
+        &EventCache<E>,
+        &mut InputQueue<E>
+    )>,
+    config: Res<InputQueueConfig>,
+    server_tick: Res<ServerTick>,
+    mut feedbacks: EventWriter<SendInputQueueFeedback<E>>
+) {
+    for (net_e, cache, mut queue) in query.iter_mut() {
+        let Some((missed, dropped)) = queue.take_feedback(config.feedback_interval) else {
+            continue;
+        };
+
+        feedbacks.send(SendInputQueueFeedback{
+            mode: SendMode::Direct(net_e.client_id()),
+            event: InputQueueFeedback::new(
+                cache.frontier_len(),
+                config.target_depth,
+                missed,
+                dropped,
+                server_tick.get()
+            )
+        });
+    }
+}
+
+pub(crate) fn time_dilation_system<E: NetworkMovement>(
+    mut feedbacks: EventReader<InputQueueFeedback<E>>,
+    mut dilation: ResMut<TimeDilation>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+    config: Res<TimeDilationConfig>
+) {
+    let nominal = *dilation.nominal_timestep
+    .get_or_insert(fixed_time.timestep().as_secs_f64());
+
+    for f in feedbacks.read() {
+        let mut error = f.depth as f64 - f.target_depth as f64;
+        if f.missed > 0 {
+            // buffer was empty, client is behind anyway
+            error = error.min(-(f.missed as f64));
+        }
+
+        // positive error means too many inputs are buffered, slow down
+        dilation.dilation = (error * config.gain)
+        .clamp(-config.max_dilation, config.max_dilation);
+        fixed_time.set_timestep_seconds(nominal * (1.0 + dilation.dilation));
+        debug!(
+            "input buffer depth: {} target: {} missed: {} dropped: {}, dilation: {}",
+            f.depth,
+            f.target_depth,
+            f.missed,
+            f.dropped,
+            dilation.dilation
+        );
+    }
+}
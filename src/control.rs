@@ -10,6 +10,7 @@ pub use network_movement::*;
 pub(crate) use systems::*;
 pub(crate) use physics_systems::*;
 
+use serde::{Serialize, de::DeserializeOwned};
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 use crate::prelude::*;
@@ -29,12 +30,14 @@ where T: NetworkTranslation{
         init: Vec3,
         axis: TranslationAxis, 
         tick: u32,
+        timestamp: f64,
         cache_size: usize
     ) -> anyhow::Result<Self> {
         let translation = T::from_vec3(init, axis);
         let cache = ComponentCache::with_init(
             translation,
             tick,
+            timestamp,
             cache_size
         )?;
         
@@ -61,12 +64,14 @@ where R: NetworkRotation {
         init: Quat, 
         axis: RotationAxis,
         tick: u32,
+        timestamp: f64,
         cache_size: usize
     ) -> anyhow::Result<Self> {
         let rotation = R::from_quat(init, axis);
         let cache = ComponentCache::with_init(
             rotation,
             tick,
+            timestamp,
             cache_size
         )?;
       
@@ -92,12 +97,14 @@ where T: NetworkTranslation {
         init: Vec3,
         axis: TranslationAxis,
         tick: u32,
+        timestamp: f64,
         cache_size: usize
     ) -> anyhow::Result<Self> {
         let translation = T::from_vec3(init, axis);
         let cache = ComponentCache::with_init(
             translation, 
             tick, 
+            timestamp,
             cache_size
         )?;
 
@@ -122,12 +129,14 @@ where R: NetworkRotation {
         init: Quat,
         axis: RotationAxis,
         tick: u32,
+        timestamp: f64,
         cache_size: usize,
     ) -> anyhow::Result<Self> {
         let rotation = R::from_quat(init, axis);
         let cache = ComponentCache::with_init(
             rotation, 
             tick, 
+            timestamp,
             cache_size
         )?;
 
@@ -138,6 +147,119 @@ where R: NetworkRotation {
     }
 }
 
+#[derive(Bundle)]
+pub struct NetworkScaleBundle<S>
+where S: NetworkScale {
+    pub scale: S,
+    pub cache: ComponentCache<S>
+}
+
+impl<S> NetworkScaleBundle<S>
+where S: NetworkScale {
+    #[inline]
+    pub fn new(
+        init: Vec3,
+        tick: u32,
+        timestamp: f64,
+        cache_size: usize,
+    ) -> anyhow::Result<Self> {
+        let scale = S::from_vec3(init);
+        let cache = ComponentCache::with_init(
+            scale, 
+            tick, 
+            timestamp,
+            cache_size
+        )?;
+
+        Ok(Self { 
+            scale, 
+            cache
+        })
+    }
+}
+
+#[derive(Bundle)]
+pub struct NetworkTransformBundle<N>
+where N: NetworkTransform {
+    pub transform: N,
+    pub cache: ComponentCache<N>
+}
+
+impl<N> NetworkTransformBundle<N>
+where N: NetworkTransform {
+    #[inline]
+    pub fn new(
+        init: &Transform,
+        axis: &TransformAxis,
+        tick: u32,
+        timestamp: f64,
+        cache_size: usize,
+    ) -> anyhow::Result<Self> {
+        let transform = N::from_transform(init, axis);
+        let cache = ComponentCache::with_init(
+            transform, 
+            tick, 
+            timestamp,
+            cache_size
+        )?;
+
+        Ok(Self { 
+            transform, 
+            cache
+        })
+    }
+}
+
+#[derive(Bundle)]
+pub struct NetworkPredictedBundle<C>
+where C: Component + Serialize + DeserializeOwned + Clone {
+    pub component: C,
+    pub cache: ComponentCache<C>,
+    pub prediction_error: PredioctionError<C>
+}
+
+impl<C> NetworkPredictedBundle<C>
+where C: Component + Serialize + DeserializeOwned + Clone {
+    #[inline]
+    pub fn new(
+        init: C,
+        tick: u32,
+        timestamp: f64,
+        cache_size: usize
+    ) -> anyhow::Result<Self> {
+        let cache = ComponentCache::with_init(
+            init.clone(), 
+            tick, 
+            timestamp,
+            cache_size
+        )?;
+
+        Ok(Self { 
+            component: init, 
+            cache, 
+            prediction_error: default() 
+        })
+    }
+}
+
+#[derive(Bundle)]
+pub struct HermiteInterpolationBundle<L>
+where L: NetworkLinearVelocity {
+    pub marker: HermiteInterpolation,
+    pub velocity_cache: ComponentCache<L>
+}
+
+impl<L> HermiteInterpolationBundle<L>
+where L: NetworkLinearVelocity {
+    #[inline]
+    pub fn new(cache_size: usize) -> Self {
+        Self { 
+            marker: HermiteInterpolation, 
+            velocity_cache: ComponentCache::with_capacity(cache_size) 
+        }
+    }
+}
+
 #[derive(Bundle)]
 pub struct CharacterControllerBundle {
     pub character_controller: KinematicCharacterController,
@@ -3,13 +3,211 @@ use anyhow::bail;
 use bevy::prelude::*;
 use crate::prelude::*;
 
+/// set of game declared actions held in one movement
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ActionFlags(u16);
+
+impl ActionFlags {
+    #[inline]
+    pub fn with<A: NetworkAction>(mut self, action: A) -> Self {
+        self.set(action, true);
+        self
+    }
+
+    #[inline]
+    pub fn set<A: NetworkAction>(&mut self, action: A, pressed: bool) {
+        let mask = Self::mask(action);
+        if pressed {
+            self.0 |= mask;
+        } else {
+            self.0 &= !mask;
+        }
+    }
+
+    #[inline]
+    pub fn contains<A: NetworkAction>(&self, action: A) -> bool {
+        self.0 & Self::mask(action) != 0
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline]
+    fn mask<A: NetworkAction>(action: A) -> u16 {
+        let bit = action.bit();
+        debug_assert!(bit < 16, "action bit: {bit} is out of 16 bits");
+        1 << bit
+    }
+}
+
+/// linear axis is normalized input, 1/127 per step
+const LINEAR_AXIS_SCALE: f32 = 127.0;
+/// rotation axis is raw pointer delta, 1/32 per step inside +-1023
+const ROTATION_AXIS_SCALE: f32 = 32.0;
+
+#[inline]
+fn pack_linear_axis(axis: Vec2) -> [i8; 2] {
+    let q = (axis.clamp(Vec2::NEG_ONE, Vec2::ONE) * LINEAR_AXIS_SCALE).round();
+    [q.x as i8, q.y as i8]
+}
+
+#[inline]
+fn unpack_linear_axis(packed: [i8; 2]) -> Vec2 {
+    Vec2::new(packed[0] as f32, packed[1] as f32) / LINEAR_AXIS_SCALE
+}
+
+#[inline]
+fn pack_rotation_axis(axis: Vec2) -> [i16; 2] {
+    let max = i16::MAX as f32;
+    let q = (axis * ROTATION_AXIS_SCALE).round()
+    .clamp(Vec2::splat(-max), Vec2::splat(max));
+    [q.x as i16, q.y as i16]
+}
+
+#[inline]
+fn unpack_rotation_axis(packed: [i16; 2]) -> Vec2 {
+    Vec2::new(packed[0] as f32, packed[1] as f32) / ROTATION_AXIS_SCALE
+}
+
+/// full index is sent every this indices,
+/// so receiver can resync after a long gap
+const FULL_INDEX_INTERVAL: u64 = 1024;
+
+/// index is sent as its low 16 bits, receiver unwraps it 
+/// against its latest index while they are within half of u16 range
+#[derive(Serialize, Deserialize, Clone, Copy)]
+struct PackedIndex {
+    low: u16,
+    full: Option<u64>
+}
+
+impl PackedIndex {
+    #[inline]
+    fn pack(index: u64) -> Self {
+        let full = (index % FULL_INDEX_INTERVAL == 0).then_some(index);
+        Self{ low: index as u16, full }
+    }
+
+    /// low bits only until unwrapped, see unwrap_index
+    #[inline]
+    fn unpack(&self) -> u64 {
+        self.full.unwrap_or(self.low as u64)
+    }
+}
+
+/// nearest index to latest with same low 16 bits
+#[inline]
+fn unwrap_index(index: u64, latest_index: usize) -> u64 {
+    if index > u16::MAX as u64 {
+        // full one
+        return index;
+    }
+
+    let latest = latest_index as u64;
+    let delta = (index as u16).wrapping_sub(latest as u16) as i16;
+    latest.saturating_add_signed(delta as i64)
+}
+
+/// wire layout of NetworkMovement2D
+#[derive(Serialize, Deserialize)]
+struct PackedMovement2D {
+    current_translation: Vec2,
+    current_angle: f32,
+    linear_axis: [i8; 2],
+    rotation_axis: [i16; 2],
+    actions: ActionFlags,
+    index: PackedIndex,
+    tick: u32
+}
+
+impl From<NetworkMovement2D> for PackedMovement2D {
+    #[inline]
+    fn from(m: NetworkMovement2D) -> Self {
+        Self {
+            current_translation: m.current_translation,
+            current_angle: m.current_angle,
+            linear_axis: pack_linear_axis(m.linear_axis),
+            rotation_axis: pack_rotation_axis(m.rotation_axis),
+            actions: m.actions,
+            index: PackedIndex::pack(m.index),
+            tick: m.tick
+        }
+    }
+}
+
+impl From<PackedMovement2D> for NetworkMovement2D {
+    #[inline]
+    fn from(p: PackedMovement2D) -> Self {
+        Self {
+            current_translation: p.current_translation,
+            current_angle: p.current_angle,
+            linear_axis: unpack_linear_axis(p.linear_axis),
+            rotation_axis: unpack_rotation_axis(p.rotation_axis),
+            actions: p.actions,
+            index: p.index.unpack(),
+            tick: p.tick
+        }
+    }
+}
+
+/// wire layout of NetworkMovement2_5D
+#[derive(Serialize, Deserialize)]
+struct PackedMovement2_5D {
+    current_translation: Vec3,
+    current_angle: f32,
+    current_vertical_speed: f32,
+    linear_axis: [i8; 2],
+    rotation_axis: [i16; 2],
+    actions: ActionFlags,
+    index: PackedIndex,
+    tick: u32
+}
+
+impl From<NetworkMovement2_5D> for PackedMovement2_5D {
+    #[inline]
+    fn from(m: NetworkMovement2_5D) -> Self {
+        Self {
+            current_translation: m.current_translation,
+            current_angle: m.current_angle,
+            current_vertical_speed: m.current_vertical_speed,
+            linear_axis: pack_linear_axis(m.linear_axis),
+            rotation_axis: pack_rotation_axis(m.rotation_axis),
+            actions: m.actions,
+            index: PackedIndex::pack(m.index),
+            tick: m.tick
+        }
+    }
+}
+
+impl From<PackedMovement2_5D> for NetworkMovement2_5D {
+    #[inline]
+    fn from(p: PackedMovement2_5D) -> Self {
+        Self {
+            current_translation: p.current_translation,
+            current_angle: p.current_angle,
+            current_vertical_speed: p.current_vertical_speed,
+            linear_axis: unpack_linear_axis(p.linear_axis),
+            rotation_axis: unpack_rotation_axis(p.rotation_axis),
+            actions: p.actions,
+            index: p.index.unpack(),
+            tick: p.tick
+        }
+    }
+}
+
+/// sent bit-packed, axes are quantized and clamped on the wire.
+/// carries no predicted state other than translation and rotation,
+/// NetworkPredicted components need a game defined movement
 #[derive(Event, Serialize, Deserialize, Clone, Default)]
+#[serde(from = "PackedMovement2D", into = "PackedMovement2D")]
 pub struct NetworkMovement2D {
     pub current_translation: Vec2,
     pub current_angle: f32,
     pub linear_axis: Vec2,
     pub rotation_axis: Vec2,
-    pub bits: u16,
+    pub actions: ActionFlags,
     pub index: u64,
     pub tick: u32
 }
@@ -42,6 +240,11 @@ impl NetworkEvent for NetworkMovement2D {
 
         Ok(())
     }
+
+    #[inline]
+    fn unwrap_index(&mut self, latest_index: usize) {
+        self.index = unwrap_index(self.index, latest_index);
+    }
 }
 
 impl NetworkMovement for NetworkMovement2D {
@@ -74,13 +277,18 @@ impl NetworkMovement for NetworkMovement2D {
     }
 }
 
+/// sent bit-packed, axes are quantized and clamped on the wire.
+/// besides translation and rotation, carries vertical speed before this movement,
+/// so jump and fall can be predicted with NetworkPredicted reading it in from_movement
 #[derive(Event, Serialize, Deserialize, Clone, Default)]
+#[serde(from = "PackedMovement2_5D", into = "PackedMovement2_5D")]
 pub struct NetworkMovement2_5D {
     pub current_translation: Vec3,
     pub current_angle: f32,
+    pub current_vertical_speed: f32,
     pub linear_axis: Vec2,
     pub rotation_axis: Vec2,
-    pub bits: u16,
+    pub actions: ActionFlags,
     pub index: u64,
     pub tick: u32
 }
@@ -104,6 +312,9 @@ impl NetworkEvent for NetworkMovement2_5D {
         if !self.current_angle.is_finite() {
             bail!("failed to validate current rotation");
         }
+        if !self.current_vertical_speed.is_finite() {
+            bail!("failed to validate current vertical speed");
+        }
         if !self.linear_axis.is_finite() {
             bail!("failed to validate linear axis");
         }
@@ -113,6 +324,11 @@ impl NetworkEvent for NetworkMovement2_5D {
 
         Ok(())
     }
+
+    #[inline]
+    fn unwrap_index(&mut self, latest_index: usize) {
+        self.index = unwrap_index(self.index, latest_index);
+    }
 }
 
 impl NetworkMovement for NetworkMovement2_5D {
@@ -131,4 +347,97 @@ impl NetworkMovement for NetworkMovement2_5D {
             RotationAxis::Z => Quat::from_rotation_z(self.current_angle.to_radians())
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy)]
+    enum TestAction {
+        Jump,
+        Fire = 15
+    }
+
+    impl NetworkAction for TestAction {
+        fn bit(self) -> u8 {
+            self as u8
+        }
+    }
+
+    fn movement(index: u64) -> NetworkMovement2_5D {
+        NetworkMovement2_5D{
+            current_translation: Vec3::new(1.5, -2.0, 3.25),
+            current_angle: 90.0,
+            current_vertical_speed: -4.5,
+            linear_axis: Vec2::new(1.0, -0.25),
+            rotation_axis: Vec2::new(12.5, -3.0),
+            actions: ActionFlags::default()
+            .with(TestAction::Fire),
+            index,
+            tick: 1_000_000
+        }
+    }
+
+    fn round_trip(m: &NetworkMovement2_5D, latest_index: usize) -> NetworkMovement2_5D {
+        let bytes = bincode::serialize(m).expect("could not serialize movement");
+        let mut decoded: NetworkMovement2_5D = bincode::deserialize(&bytes)
+        .expect("could not deserialize movement");
+        decoded.unwrap_index(latest_index);
+        decoded
+    }
+
+    #[test]
+    fn movement_round_trip() {
+        let m = movement(17);
+        let decoded = round_trip(&m, 10);
+
+        assert_eq!(decoded.current_translation, m.current_translation);
+        assert_eq!(decoded.current_angle, m.current_angle);
+        assert_eq!(decoded.current_vertical_speed, m.current_vertical_speed);
+        assert!((decoded.linear_axis - m.linear_axis).abs().max_element() <= 0.5 / LINEAR_AXIS_SCALE);
+        assert!((decoded.rotation_axis - m.rotation_axis).abs().max_element() <= 0.5 / ROTATION_AXIS_SCALE);
+        assert_eq!(decoded.actions, m.actions);
+        assert_eq!(decoded.index, m.index);
+        assert_eq!(decoded.tick, m.tick);
+    }
+
+    #[test]
+    fn action_flags_round_trip() {
+        let mut m = movement(1);
+        m.actions.set(TestAction::Jump, true);
+        m.actions.set(TestAction::Fire, false);
+
+        let decoded = round_trip(&m, 0);
+        assert!(decoded.actions.contains(TestAction::Jump));
+        assert!(!decoded.actions.contains(TestAction::Fire));
+
+        m.actions = ActionFlags::default();
+        assert!(round_trip(&m, 0).actions.is_empty());
+    }
+
+    #[test]
+    fn index_unwraps_across_u16_boundary() {
+        let base = u16::MAX as u64 + 1;
+        for (index, latest) in [
+            (base - 1, base as usize - 10),
+            (base + 3, base as usize - 10),
+            (base * 7 + 5, base as usize * 7 - 2),
+            // late one behind latest
+            (base * 3 - 2, base as usize * 3 + 4)
+        ] {
+            assert_eq!(round_trip(&movement(index), latest).index, index, "index: {index}");
+        }
+    }
+
+    #[test]
+    fn index_falls_back_to_full() {
+        let full = FULL_INDEX_INTERVAL * 1000;
+        assert!(full > u16::MAX as u64);
+
+        // latest is far behind, only full one can be restored
+        assert_eq!(round_trip(&movement(full), 0).index, full);
+        assert_ne!(round_trip(&movement(full - 1), 0).index, full - 1);
+        assert_eq!(round_trip(&movement(full - 1), full as usize).index, full - 1);
+    }
+}
@@ -1,3 +1,4 @@
+use std::marker::PhantomData;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use crate::prelude::*;
@@ -31,6 +32,26 @@ impl NetworkTranslation for NetworkTranslation2D {
     }
 }
 
+impl NetworkDelta for NetworkTranslation2D {
+    type Delta = [i16; 2];
+
+    #[inline]
+    fn delta(&self, baseline: &Self, step: f32) -> Option<Self::Delta> {
+        Some([
+            delta_steps(self.0.x, baseline.0.x, step)?,
+            delta_steps(self.0.y, baseline.0.y, step)?
+        ])
+    }
+
+    #[inline]
+    fn apply_delta(baseline: &Self, delta: &Self::Delta, step: f32) -> Self {
+        Self(Vec2::new(
+            apply_delta_steps(baseline.0.x, delta[0], step),
+            apply_delta_steps(baseline.0.y, delta[1], step)
+        ))
+    }
+}
+
 #[derive(Component, Serialize, Deserialize, Default, Clone, Copy)]
 pub struct NetworkTranslation3D(pub Vec3);
 
@@ -52,6 +73,184 @@ impl NetworkTranslation for NetworkTranslation3D {
     }
 }
 
+impl NetworkDelta for NetworkTranslation3D {
+    type Delta = [i16; 3];
+
+    #[inline]
+    fn delta(&self, baseline: &Self, step: f32) -> Option<Self::Delta> {
+        Some([
+            delta_steps(self.0.x, baseline.0.x, step)?,
+            delta_steps(self.0.y, baseline.0.y, step)?,
+            delta_steps(self.0.z, baseline.0.z, step)?
+        ])
+    }
+
+    #[inline]
+    fn apply_delta(baseline: &Self, delta: &Self::Delta, step: f32) -> Self {
+        Self(Vec3::new(
+            apply_delta_steps(baseline.0.x, delta[0], step),
+            apply_delta_steps(baseline.0.y, delta[1], step),
+            apply_delta_steps(baseline.0.z, delta[2], step)
+        ))
+    }
+}
+
+#[inline]
+fn quantize_u16(v: f32, half_extent: f32) -> u16 {
+    let normalized = ((v + half_extent) / (half_extent * 2.0)).clamp(0.0, 1.0);
+    (normalized * u16::MAX as f32).round() as u16
+}
+
+#[inline]
+fn dequantize_u16(q: u16, half_extent: f32) -> f32 {
+    q as f32 / u16::MAX as f32 * (half_extent * 2.0) - half_extent
+}
+
+/// region quantized translations are encoded in, 
+/// positions outside of it are clamped to its edge
+pub trait QuantizationCell: Send + Sync + 'static {
+    /// center of the cell in world space
+    const ORIGIN: Vec3;
+    /// cell covers ORIGIN -/+ this meters on each axis
+    const HALF_EXTENT: f32;
+
+    #[inline]
+    fn step() -> f32 {
+        Self::HALF_EXTENT * 2.0 / u16::MAX as f32
+    }
+
+    #[inline]
+    fn contains(local: Vec3) -> bool {
+        local.abs().max_element() <= Self::HALF_EXTENT
+    }
+}
+
+/// 16 bits per axis inside the cell, precision is Q::step().
+/// in 2D, ORIGIN component on the dropped axis is not encoded
+#[derive(Component, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct NetworkTranslation2DQ16<Q: QuantizationCell>(
+    pub [u16; 2], 
+    #[serde(skip)] PhantomData<Q>
+);
+
+impl<Q: QuantizationCell> Clone for NetworkTranslation2DQ16<Q> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Q: QuantizationCell> Copy for NetworkTranslation2DQ16<Q> {}
+
+impl<Q: QuantizationCell> Default for NetworkTranslation2DQ16<Q> {
+    #[inline]
+    fn default() -> Self {
+        Self::from_vec3(Q::ORIGIN, TranslationAxis::Default)
+    }
+}
+
+impl<Q: QuantizationCell> NetworkTranslation for NetworkTranslation2DQ16<Q> {
+    #[inline]
+    fn from_vec3(vec3: Vec3, axis: TranslationAxis) -> Self {
+        let local = NetworkTranslation2D::from_vec3(vec3 - Q::ORIGIN, axis).0;
+        let e = Q::HALF_EXTENT;
+        Self([quantize_u16(local.x, e), quantize_u16(local.y, e)], PhantomData)
+    }
+
+    #[inline]
+    fn to_vec3(&self, axis: TranslationAxis) -> Vec3 {
+        let e = Q::HALF_EXTENT;
+        let local = NetworkTranslation2D(Vec2::new(
+            dequantize_u16(self.0[0], e), 
+            dequantize_u16(self.0[1], e)
+        )).to_vec3(axis);
+        local + NetworkTranslation2D::from_vec3(Q::ORIGIN, axis).to_vec3(axis)
+    }
+
+    #[inline]
+    fn interpolate(&self, rhs: &Self, s: f32, axis: TranslationAxis) 
+    -> Vec3 {
+        self.to_vec3(axis)
+        .lerp(rhs.to_vec3(axis), s)
+    }
+
+    #[inline]
+    fn quantization_step() -> f32 {
+        Q::step()
+    }
+
+    #[inline]
+    fn is_in_range(vec3: Vec3, axis: TranslationAxis) -> bool {
+        let local = NetworkTranslation2D::from_vec3(vec3 - Q::ORIGIN, axis).0;
+        Q::contains(local.extend(0.0))
+    }
+}
+
+/// 16 bits per axis inside the cell, precision is Q::step()
+#[derive(Component, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct NetworkTranslation3DQ16<Q: QuantizationCell>(
+    pub [u16; 3], 
+    #[serde(skip)] PhantomData<Q>
+);
+
+impl<Q: QuantizationCell> Clone for NetworkTranslation3DQ16<Q> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Q: QuantizationCell> Copy for NetworkTranslation3DQ16<Q> {}
+
+impl<Q: QuantizationCell> Default for NetworkTranslation3DQ16<Q> {
+    #[inline]
+    fn default() -> Self {
+        Self::from_vec3(Q::ORIGIN, TranslationAxis::Default)
+    }
+}
+
+impl<Q: QuantizationCell> NetworkTranslation for NetworkTranslation3DQ16<Q> {
+    #[inline]
+    fn from_vec3(vec3: Vec3, _: TranslationAxis) -> Self {
+        let local = vec3 - Q::ORIGIN;
+        let e = Q::HALF_EXTENT;
+        Self([
+            quantize_u16(local.x, e), 
+            quantize_u16(local.y, e), 
+            quantize_u16(local.z, e)
+        ], PhantomData)
+    }
+
+    #[inline]
+    fn to_vec3(&self, _: TranslationAxis) -> Vec3 {
+        let e = Q::HALF_EXTENT;
+        Vec3::new(
+            dequantize_u16(self.0[0], e), 
+            dequantize_u16(self.0[1], e), 
+            dequantize_u16(self.0[2], e)
+        ) + Q::ORIGIN
+    }
+
+    #[inline]
+    fn interpolate(&self, rhs: &Self, s: f32, axis: TranslationAxis) 
+    -> Vec3 {
+        self.to_vec3(axis)
+        .lerp(rhs.to_vec3(axis), s)
+    }
+
+    #[inline]
+    fn quantization_step() -> f32 {
+        Q::step()
+    }
+
+    #[inline]
+    fn is_in_range(vec3: Vec3, _: TranslationAxis) -> bool {
+        Q::contains(vec3 - Q::ORIGIN)
+    }
+}
+
 #[derive(Component, Serialize, Deserialize, Default, Clone, Copy)]
 pub struct NetworkAngleDegrees(pub f32);
 
@@ -91,6 +290,20 @@ impl NetworkRotation for NetworkAngleDegrees {
     }
 }
 
+impl NetworkDelta for NetworkAngleDegrees {
+    type Delta = i16;
+
+    #[inline]
+    fn delta(&self, baseline: &Self, step: f32) -> Option<Self::Delta> {
+        delta_steps(self.0, baseline.0, step)
+    }
+
+    #[inline]
+    fn apply_delta(baseline: &Self, delta: &Self::Delta, step: f32) -> Self {
+        Self(apply_delta_steps(baseline.0, *delta, step))
+    }
+}
+
 #[derive(Component, Serialize, Deserialize, Default, Clone, Copy)]
 pub struct NetworkEuler(pub Vec3);
 
@@ -127,3 +340,523 @@ impl NetworkRotation for NetworkEuler {
         )
     }
 }
+
+impl NetworkDelta for NetworkEuler {
+    type Delta = [i16; 3];
+
+    #[inline]
+    fn delta(&self, baseline: &Self, step: f32) -> Option<Self::Delta> {
+        Some([
+            delta_steps(self.0.x, baseline.0.x, step)?,
+            delta_steps(self.0.y, baseline.0.y, step)?,
+            delta_steps(self.0.z, baseline.0.z, step)?
+        ])
+    }
+
+    #[inline]
+    fn apply_delta(baseline: &Self, delta: &Self::Delta, step: f32) -> Self {
+        Self(Vec3::new(
+            apply_delta_steps(baseline.0.x, delta[0], step),
+            apply_delta_steps(baseline.0.y, delta[1], step),
+            apply_delta_steps(baseline.0.z, delta[2], step)
+        ))
+    }
+}
+
+/// yaw wrapped into 16 bits, 65536 steps per turn
+#[derive(Component, Serialize, Deserialize, Default, Clone, Copy)]
+pub struct NetworkYaw16(pub u16);
+
+impl NetworkYaw16 {
+    const STEPS: f32 = 65536.0;
+
+    #[inline]
+    pub fn from_degrees(degrees: f32) -> Self {
+        let turns = (degrees / 360.0).rem_euclid(1.0);
+        Self(((turns * Self::STEPS).round() as u32 % 65536) as u16)
+    }
+
+    #[inline]
+    pub fn to_degrees(&self) -> f32 {
+        self.0 as f32 / Self::STEPS * 360.0
+    }
+}
+
+impl NetworkRotation for NetworkYaw16 {
+    #[inline]
+    fn from_quat(quat: Quat, axis: RotationAxis) -> Self {
+        Self::from_degrees(NetworkAngleDegrees::from_quat(quat, axis).0)
+    }
+
+    #[inline]
+    fn to_quat(&self, axis: RotationAxis) -> Quat {
+        NetworkAngleDegrees(self.to_degrees()).to_quat(axis)
+    }
+
+    #[inline]
+    fn interpolate(&self, rhs: &Self, t: f32, axis: RotationAxis) 
+    -> Quat {
+        // wrapping difference is always the shortest path
+        let delta = rhs.0.wrapping_sub(self.0) as i16 as f32;
+        let degrees = self.to_degrees() + delta / Self::STEPS * 360.0 * t;
+        NetworkAngleDegrees(degrees).to_quat(axis)
+    }
+}
+
+const SMALLEST_THREE_RANGE: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+fn encode_smallest_three(quat: Quat, bits: u32) -> u64 {
+    let q = quat.normalize()
+    .to_array();
+    let largest = (0..4).max_by(|l, r| 
+        q[*l].abs().total_cmp(&q[*r].abs())
+    )
+    .unwrap();
+    // q and -q are same rotation, keep largest positive to drop its sign
+    let sign = if q[largest] < 0.0 { -1.0 } else { 1.0 };
+    let max = ((1u64 << bits) - 1) as f32;
+
+    let mut packed = largest as u64;
+    for (i, v) in q.iter().enumerate() {
+        if i == largest {
+            continue;
+        }
+
+        let normalized = ((v * sign + SMALLEST_THREE_RANGE) / (SMALLEST_THREE_RANGE * 2.0))
+        .clamp(0.0, 1.0);
+        packed = (packed << bits) | (normalized * max).round() as u64;
+    }
+    packed
+}
+
+fn decode_smallest_three(packed: u64, bits: u32) -> Quat {
+    let mask = (1u64 << bits) - 1;
+    let max = mask as f32;
+    let largest = ((packed >> (bits * 3)) & 0b11) as usize;
+
+    let mut q = [0.0f32; 4];
+    let mut sum = 0.0;
+    let mut k = 3;
+    for (i, v) in q.iter_mut().enumerate() {
+        if i == largest {
+            continue;
+        }
+
+        k -= 1;
+        let quantized = (packed >> (bits * k)) & mask;
+        *v = quantized as f32 / max * (SMALLEST_THREE_RANGE * 2.0) - SMALLEST_THREE_RANGE;
+        sum += *v * *v;
+    }
+    q[largest] = (1.0 - sum).max(0.0).sqrt();
+
+    Quat::from_array(q).normalize()
+}
+
+#[inline]
+fn slerp_shortest(from: Quat, to: Quat, t: f32) -> Quat {
+    let to = if from.dot(to) < 0.0 { -to } else { to };
+    from.slerp(to, t)
+}
+
+/// smallest three quaternion, 2 bits index and 10 bits per component
+#[derive(Component, Serialize, Deserialize, Clone, Copy)]
+pub struct NetworkQuat32(pub u32);
+
+impl Default for NetworkQuat32 {
+    #[inline]
+    fn default() -> Self {
+        Self::from_quat(Quat::IDENTITY, default())
+    }
+}
+
+impl NetworkRotation for NetworkQuat32 {
+    #[inline]
+    fn from_quat(quat: Quat, _: RotationAxis) -> Self {
+        Self(encode_smallest_three(quat, 10) as u32)
+    }
+
+    #[inline]
+    fn to_quat(&self, _: RotationAxis) -> Quat {
+        decode_smallest_three(self.0 as u64, 10)
+    }
+
+    #[inline]
+    fn interpolate(&self, rhs: &Self, per: f32, axis: RotationAxis) 
+    -> Quat {
+        slerp_shortest(self.to_quat(axis), rhs.to_quat(axis), per)
+    }
+}
+
+/// smallest three quaternion, 2 bits index and 15 bits per component
+#[derive(Component, Serialize, Deserialize, Clone, Copy)]
+pub struct NetworkQuat48(pub [u16; 3]);
+
+impl Default for NetworkQuat48 {
+    #[inline]
+    fn default() -> Self {
+        Self::from_quat(Quat::IDENTITY, default())
+    }
+}
+
+impl NetworkRotation for NetworkQuat48 {
+    #[inline]
+    fn from_quat(quat: Quat, _: RotationAxis) -> Self {
+        let packed = encode_smallest_three(quat, 15);
+        Self([
+            (packed >> 32) as u16,
+            (packed >> 16) as u16,
+            packed as u16
+        ])
+    }
+
+    #[inline]
+    fn to_quat(&self, _: RotationAxis) -> Quat {
+        let packed = (self.0[0] as u64) << 32 
+        | (self.0[1] as u64) << 16 
+        | self.0[2] as u64;
+        decode_smallest_three(packed, 15)
+    }
+
+    #[inline]
+    fn interpolate(&self, rhs: &Self, per: f32, axis: RotationAxis) 
+    -> Quat {
+        slerp_shortest(self.to_quat(axis), rhs.to_quat(axis), per)
+    }
+}
+
+#[derive(Component, Serialize, Deserialize, Clone, Copy)]
+pub struct NetworkUniformScale(pub f32);
+
+impl Default for NetworkUniformScale {
+    #[inline]
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl NetworkScale for NetworkUniformScale {
+    #[inline]
+    fn from_vec3(vec3: Vec3) -> Self {
+        Self((vec3.x + vec3.y + vec3.z) / 3.0)
+    }
+
+    #[inline]
+    fn to_vec3(&self) -> Vec3 {
+        Vec3::splat(self.0)
+    }
+
+    #[inline]
+    fn interpolate(&self, rhs: &Self, s: f32) -> Vec3 {
+        Vec3::splat(self.0.lerp(rhs.0, s))
+    }
+}
+
+#[derive(Component, Serialize, Deserialize, Clone, Copy)]
+pub struct NetworkScale3D(pub Vec3);
+
+impl Default for NetworkScale3D {
+    #[inline]
+    fn default() -> Self {
+        Self(Vec3::ONE)
+    }
+}
+
+impl NetworkScale for NetworkScale3D {
+    #[inline]
+    fn from_vec3(vec3: Vec3) -> Self {
+        Self(vec3)
+    }
+
+    #[inline]
+    fn to_vec3(&self) -> Vec3 {
+        self.0
+    }
+
+    #[inline]
+    fn interpolate(&self, rhs: &Self, s: f32) -> Vec3 {
+        self.0.lerp(rhs.0, s)
+    }
+}
+
+/// translation and rotation comparison shared by TR and TRS
+#[inline]
+fn exceeds_tr_threshold<T, R>(
+    translation: &T, 
+    rotation: &R, 
+    transform: &Transform, 
+    config: &ReplicationConfig, 
+    axis: &TransformAxis
+) -> bool
+where 
+T: NetworkTranslation,
+R: NetworkRotation {
+    let threshold = config.translation_threshold.max(T::quantization_step());
+    translation.to_vec3(axis.translation)
+    .distance_squared(transform.translation) > threshold * threshold
+    || rotation.to_quat(axis.rotation)
+    .normalize()
+    .angle_between(transform.rotation.normalize()) 
+    > config.rotation_threashold.to_radians()
+}
+
+#[derive(Component, Serialize, Deserialize, Default, Clone, Copy)]
+pub struct NetworkTransformTR<T, R>
+where 
+T: NetworkTranslation,
+R: NetworkRotation {
+    pub translation: T,
+    pub rotation: R
+}
+
+impl<T, R> NetworkTransform for NetworkTransformTR<T, R>
+where 
+T: NetworkTranslation,
+R: NetworkRotation {
+    #[inline]
+    fn from_transform(transform: &Transform, axis: &TransformAxis) -> Self {
+        Self { 
+            translation: T::from_vec3(transform.translation, axis.translation), 
+            rotation: R::from_quat(transform.rotation, axis.rotation) 
+        }
+    }
+
+    #[inline]
+    fn write(&self, transform: &mut Transform, axis: &TransformAxis) {
+        transform.translation = self.translation.to_vec3(axis.translation);
+        transform.rotation = self.rotation.to_quat(axis.rotation);
+    }
+
+    #[inline]
+    fn write_interpolated(
+        &self, 
+        rhs: &Self, 
+        s: f32, 
+        transform: &mut Transform, 
+        axis: &TransformAxis
+    ) {
+        transform.translation = self.translation
+        .interpolate(&rhs.translation, s, axis.translation);
+        transform.rotation = self.rotation
+        .interpolate(&rhs.rotation, s, axis.rotation);
+    }
+
+    #[inline]
+    fn exceeds_threshold(
+        &self, 
+        transform: &Transform, 
+        config: &ReplicationConfig, 
+        axis: &TransformAxis
+    ) -> bool {
+        exceeds_tr_threshold(&self.translation, &self.rotation, transform, config, axis)
+    }
+}
+
+#[derive(Component, Serialize, Deserialize, Default, Clone, Copy)]
+pub struct NetworkTransformTRS<T, R, S>
+where 
+T: NetworkTranslation,
+R: NetworkRotation,
+S: NetworkScale {
+    pub translation: T,
+    pub rotation: R,
+    pub scale: S
+}
+
+impl<T, R, S> NetworkTransform for NetworkTransformTRS<T, R, S>
+where 
+T: NetworkTranslation,
+R: NetworkRotation,
+S: NetworkScale {
+    #[inline]
+    fn from_transform(transform: &Transform, axis: &TransformAxis) -> Self {
+        Self { 
+            translation: T::from_vec3(transform.translation, axis.translation), 
+            rotation: R::from_quat(transform.rotation, axis.rotation),
+            scale: S::from_vec3(transform.scale) 
+        }
+    }
+
+    #[inline]
+    fn write(&self, transform: &mut Transform, axis: &TransformAxis) {
+        transform.translation = self.translation.to_vec3(axis.translation);
+        transform.rotation = self.rotation.to_quat(axis.rotation);
+        transform.scale = self.scale.to_vec3();
+    }
+
+    #[inline]
+    fn write_interpolated(
+        &self, 
+        rhs: &Self, 
+        s: f32, 
+        transform: &mut Transform, 
+        axis: &TransformAxis
+    ) {
+        transform.translation = self.translation
+        .interpolate(&rhs.translation, s, axis.translation);
+        transform.rotation = self.rotation
+        .interpolate(&rhs.rotation, s, axis.rotation);
+        transform.scale = self.scale
+        .interpolate(&rhs.scale, s);
+    }
+
+    #[inline]
+    fn exceeds_threshold(
+        &self, 
+        transform: &Transform, 
+        config: &ReplicationConfig, 
+        axis: &TransformAxis
+    ) -> bool {
+        exceeds_tr_threshold(&self.translation, &self.rotation, transform, config, axis)
+        || self.scale.to_vec3()
+        .distance_squared(transform.scale) > config.scale_threshold_sq()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEGREE: f32 = std::f32::consts::PI / 180.0;
+
+    fn wrapped_diff(lhs: f32, rhs: f32) -> f32 {
+        let d = (lhs - rhs).rem_euclid(360.0);
+        d.min(360.0 - d)
+    }
+
+    fn yaw_degrees(q: Quat) -> f32 {
+        q.to_euler(EulerRot::YXZ).0.to_degrees()
+    }
+
+    #[test]
+    fn yaw16_round_trip() {
+        for degrees in [0.0, 1.0, 90.0, 179.99, 180.0, 359.99, -0.01, 720.5] {
+            let yaw = NetworkYaw16::from_degrees(degrees);
+            assert!(wrapped_diff(yaw.to_degrees(), degrees) < 0.01, "degrees: {degrees}");
+
+            let q = yaw.to_quat(RotationAxis::Y);
+            assert!(wrapped_diff(yaw_degrees(q), degrees) < 0.01, "degrees: {degrees}");
+        }
+    }
+
+    #[test]
+    fn yaw16_interpolates_across_wrap() {
+        let from = NetworkYaw16::from_degrees(359.0);
+        let to = NetworkYaw16::from_degrees(1.0);
+        let q = from.interpolate(&to, 0.5, RotationAxis::Y);
+        assert!(wrapped_diff(yaw_degrees(q), 0.0) < 0.01);
+
+        let q = to.interpolate(&from, 0.25, RotationAxis::Y);
+        assert!(wrapped_diff(yaw_degrees(q), 0.5) < 0.01);
+    }
+
+    fn sample_rotations() -> Vec<Quat> {
+        vec![
+            Quat::IDENTITY,
+            Quat::from_rotation_y(179.9 * DEGREE),
+            Quat::from_rotation_y(-179.9 * DEGREE),
+            Quat::from_rotation_x(90.0 * DEGREE),
+            Quat::from_euler(EulerRot::XYZ, 0.3, -1.2, 2.9),
+            Quat::from_axis_angle(Vec3::ONE.normalize(), 3.1),
+            -Quat::from_rotation_z(45.0 * DEGREE)
+        ]
+    }
+
+    #[test]
+    fn quat32_round_trip() {
+        for q in sample_rotations() {
+            let decoded = NetworkQuat32::from_quat(q, default())
+            .to_quat(default());
+            assert!(decoded.angle_between(q) < 0.5 * DEGREE, "quat: {q}");
+        }
+    }
+
+    #[test]
+    fn quat48_round_trip() {
+        for q in sample_rotations() {
+            let decoded = NetworkQuat48::from_quat(q, default())
+            .to_quat(default());
+            assert!(decoded.angle_between(q) < 0.1 * DEGREE, "quat: {q}");
+        }
+    }
+
+    #[test]
+    fn quat_interpolates_across_wrap() {
+        let from = Quat::from_rotation_y(179.0 * DEGREE);
+        let to = Quat::from_rotation_y(-179.0 * DEGREE);
+        let expected = Quat::from_rotation_y(180.0 * DEGREE);
+
+        let q = NetworkQuat32::from_quat(from, default())
+        .interpolate(&NetworkQuat32::from_quat(to, default()), 0.5, default());
+        assert!(q.angle_between(expected) < 0.5 * DEGREE);
+
+        let q = NetworkQuat48::from_quat(from, default())
+        .interpolate(&NetworkQuat48::from_quat(to, default()), 0.5, default());
+        assert!(q.angle_between(expected) < 0.1 * DEGREE);
+    }
+
+    struct TestCell;
+
+    impl QuantizationCell for TestCell {
+        const ORIGIN: Vec3 = Vec3::new(1000.0, -50.0, 250.0);
+        const HALF_EXTENT: f32 = 512.0;
+    }
+
+    fn sample_translations() -> Vec<Vec3> {
+        let e = TestCell::HALF_EXTENT;
+        vec![
+            TestCell::ORIGIN,
+            TestCell::ORIGIN + Vec3::new(0.123, -7.77, 300.5),
+            TestCell::ORIGIN + Vec3::splat(e),
+            TestCell::ORIGIN - Vec3::splat(e),
+            TestCell::ORIGIN + Vec3::new(e - 0.001, -e + 0.001, 0.5)
+        ]
+    }
+
+    #[test]
+    fn translation3d_q16_round_trip() {
+        let max_error = TestCell::step() * 0.5 + f32::EPSILON * 1024.0;
+        for v in sample_translations() {
+            let decoded = NetworkTranslation3DQ16::<TestCell>::from_vec3(v, default())
+            .to_vec3(default());
+            assert!((decoded - v).abs().max_element() <= max_error, "translation: {v}");
+        }
+    }
+
+    #[test]
+    fn translation2d_q16_round_trip() {
+        let max_error = TestCell::step() * 0.5 + f32::EPSILON * 1024.0;
+        for v in sample_translations() {
+            let decoded = NetworkTranslation2DQ16::<TestCell>::from_vec3(v, TranslationAxis::XY)
+            .to_vec3(TranslationAxis::XY);
+            let error = (decoded.truncate() - v.truncate()).abs().max_element();
+            assert!(error <= max_error, "translation: {v}");
+        }
+    }
+
+    #[test]
+    fn translation_q16_clamps_out_of_cell() {
+        let v = TestCell::ORIGIN + Vec3::new(TestCell::HALF_EXTENT * 3.0, 0.0, 0.0);
+        let decoded = NetworkTranslation3DQ16::<TestCell>::from_vec3(v, default())
+        .to_vec3(default());
+        let edge = TestCell::ORIGIN.x + TestCell::HALF_EXTENT;
+        assert!((decoded.x - edge).abs() <= TestCell::step());
+    }
+
+    #[test]
+    fn translation_q16_range() {
+        let inside = TestCell::ORIGIN + Vec3::splat(TestCell::HALF_EXTENT);
+        let outside = TestCell::ORIGIN - Vec3::new(0.0, TestCell::HALF_EXTENT + 1.0, 0.0);
+        assert!(NetworkTranslation3DQ16::<TestCell>::is_in_range(inside, default()));
+        assert!(!NetworkTranslation3DQ16::<TestCell>::is_in_range(outside, default()));
+        // dropped axis is not encoded
+        assert!(NetworkTranslation2DQ16::<TestCell>::is_in_range(outside, TranslationAxis::XZ));
+        assert!(!NetworkTranslation2DQ16::<TestCell>::is_in_range(outside, TranslationAxis::XY));
+    }
+
+    #[test]
+    fn translation_q16_default_is_origin() {
+        let decoded = NetworkTranslation3DQ16::<TestCell>::default()
+        .to_vec3(default());
+        assert!((decoded - TestCell::ORIGIN).abs().max_element() <= TestCell::step());
+    }
+}
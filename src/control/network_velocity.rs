@@ -2,7 +2,7 @@ use bevy::prelude::*;
 use serde::{Serialize, Deserialize};
 use crate::core::*;
 
-#[derive(Component, Serialize, Deserialize, Default)]
+#[derive(Component, Serialize, Deserialize, Clone, Copy, Default)]
 pub struct NetworkLinearVelocity3D(pub Vec3);
 
 impl NetworkLinearVelocity for NetworkLinearVelocity3D {
@@ -15,7 +15,7 @@ impl NetworkLinearVelocity for NetworkLinearVelocity3D {
     }
 }
 
-#[derive(Component, Serialize, Deserialize, Default)]
+#[derive(Component, Serialize, Deserialize, Clone, Copy, Default)]
 pub struct NetworkAngularVelocity3D(pub Vec3);
 
 impl NetworkAngularVelocity for NetworkAngularVelocity3D {
@@ -1,4 +1,11 @@
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    ecs::entity::EntityHashSet
+};
+use bevy_replicon::{
+    client::confirm_history::ConfirmHistory,
+    server::server_tick::ServerTick
+};
 use crate::prelude::*;
 
 pub(crate) fn cache_translation_system<T>(
@@ -29,22 +36,65 @@ where R: NetworkRotation {
     cache.cache();
 }
 
-pub(crate) fn apply_transform_translation_system<T>(
+pub(crate) fn cache_scale_system<S>(
+    mut query: Query<
+        &mut ComponentCache<S>, 
+        (With<Owning>, Changed<ComponentCache<S>>)
+    >
+)
+where S: NetworkScale {
+    let Ok(mut cache) = query.get_single_mut() else {
+        return;
+    };
+
+    cache.cache();
+}
+
+pub(crate) fn cache_network_transform_system<N>(
     mut query: Query<
-        (&Transform, &mut T, &mut ComponentCache<T>), 
-        Changed<Transform>
+        &mut ComponentCache<N>, 
+        (With<Owning>, Changed<ComponentCache<N>>)
+    >
+)
+where N: NetworkTransform {
+    let Ok(mut cache) = query.get_single_mut() else {
+        return;
+    };
+
+    cache.cache();
+}
+
+pub(crate) fn apply_transform_translation_system<T>(
+    mut query: Query<(
+        &Transform, 
+        &mut T, 
+        &mut ComponentCache<T>,
+        Option<&KeyframeReplication>
+    ), 
+        Or<(Changed<Transform>, With<KeyframeReplication>)>
     >,
     config: Res<ReplicationConfig>,
-    axis: Res<TransformAxis>
+    axis: Res<TransformAxis>,
+    server_tick: Res<ServerTick>
 )
 where T: NetworkTranslation {
-    for (transform, mut t, mut cache) in query.iter_mut() {
+    // changes under quantization step can not be represented anyway
+    let threshold = config.translation_threshold.max(T::quantization_step());
+    let tick = server_tick.get();
+    for (transform, mut t, mut cache, keyframe) in query.iter_mut() {
         match cache.latest_snapshot() {
             Some(s) => {
-                if s.component()
-                .to_vec3(axis.translation)
-                .distance_squared(transform.translation) 
-                <= config.translation_threshold_sq() {
+                let latest = s.component().to_vec3(axis.translation);
+                let exact = T::from_vec3(transform.translation, axis.translation)
+                .to_vec3(axis.translation) == latest;
+                let forced = keyframe.is_some_and(|k| 
+                    k.is_forced(s.tick(), tick)
+                );
+
+                if exact 
+                || (!forced 
+                && latest.distance_squared(transform.translation) 
+                <= threshold * threshold) {
                     cache.cache();
                     continue;
                 }
@@ -58,24 +108,64 @@ where T: NetworkTranslation {
     }
 }
 
+pub(crate) fn warn_translation_range_system<T>(
+    query: Query<(Entity, &Transform), (With<T>, Changed<Transform>)>,
+    mut removed: RemovedComponents<T>,
+    mut out_of_range: Local<EntityHashSet>,
+    axis: Res<TransformAxis>
+)
+where T: NetworkTranslation {
+    for e in removed.read() {
+        out_of_range.remove(&e);
+    }
+
+    for (e, transform) in query.iter() {
+        if T::is_in_range(transform.translation, axis.translation) {
+            out_of_range.remove(&e);
+            continue;
+        }
+
+        // once until it comes back in range
+        if out_of_range.insert(e) {
+            warn!(
+                "translation: {} of {e:?} is out of encodable range, clamped", 
+                transform.translation
+            );
+        }
+    }
+}
+
 pub(crate) fn apply_transform_rotation_system<R>(
-    mut query: Query<
-        (&Transform, &mut R, &mut ComponentCache<R>),
-        Changed<Transform>
+    mut query: Query<(
+        &Transform, 
+        &mut R, 
+        &mut ComponentCache<R>,
+        Option<&KeyframeReplication>
+    ),
+        Or<(Changed<Transform>, With<KeyframeReplication>)>
     >,
     config: Res<ReplicationConfig>,
-    axis: Res<TransformAxis>
+    axis: Res<TransformAxis>,
+    server_tick: Res<ServerTick>
 )
 where R: NetworkRotation {
-    for (transform, mut r, mut cache) in query.iter_mut() {
+    let tick = server_tick.get();
+    for (transform, mut r, mut cache, keyframe) in query.iter_mut() {
         match cache.latest_snapshot() {
             Some(s) => {
-                if s.component()
-                .to_quat(axis.rotation)
-                .normalize()
+                let latest = s.component().to_quat(axis.rotation);
+                let exact = R::from_quat(transform.rotation, axis.rotation)
+                .to_quat(axis.rotation) == latest;
+                let forced = keyframe.is_some_and(|k| 
+                    k.is_forced(s.tick(), tick)
+                );
+
+                if exact
+                || (!forced
+                && latest.normalize()
                 .angle_between(transform.rotation.normalize())
                 .abs()
-                <= config.rotation_threashold.to_radians() {
+                <= config.rotation_threashold.to_radians()) {
                     cache.cache();
                     continue;
                 }
@@ -89,6 +179,215 @@ where R: NetworkRotation {
     } 
 }
 
+pub(crate) fn warm_translation_system<T>(
+    mut query: Query<(
+        &mut Transform,
+        &T,
+        &mut ComponentCache<T>,
+        &ConfirmHistory
+    ),
+        (Added<ComponentCache<T>>, Without<Owning>)
+    >,
+    axis: Res<TransformAxis>,
+    clock: Res<NetworkClock>
+)
+where T: NetworkTranslation {
+    for (mut transform, net_trans, mut cache, confirmed) in query.iter_mut() {
+        // entity became visible again, start from replicated state not from origin
+        if cache.frontier_len() == 0 && cache.cache_len() == 0 {
+            let tick = confirmed.last_tick().get();
+            if let Err(e) = cache.insert(*net_trans, tick, clock.now()) {
+                warn!("could not warm translation cache: {e}");
+            }
+        }
+        transform.translation = net_trans.to_vec3(axis.translation);
+    }
+}
+
+pub(crate) fn teleport_translation_system<T>(
+    mut query: Query<
+        &mut ComponentCache<T>, 
+        (Changed<NetworkTeleport>, Without<Owning>)
+    >
+)
+where T: NetworkTranslation {
+    for mut cache in query.iter_mut() {
+        // only teleported snapshot remains, interpolation snaps to it
+        let frontier_len = cache.frontier_len();
+        if frontier_len > 1 {
+            cache.cache_n(frontier_len - 1);
+        }
+    }
+}
+
+pub(crate) fn apply_transform_scale_system<S>(
+    mut query: Query<(
+        &Transform, 
+        &mut S, 
+        &mut ComponentCache<S>,
+        Option<&KeyframeReplication>
+    ), 
+        Or<(Changed<Transform>, With<KeyframeReplication>)>
+    >,
+    config: Res<ReplicationConfig>,
+    server_tick: Res<ServerTick>
+)
+where S: NetworkScale {
+    let tick = server_tick.get();
+    for (transform, mut s, mut cache, keyframe) in query.iter_mut() {
+        match cache.latest_snapshot() {
+            Some(snap) => {
+                let latest = snap.component().to_vec3();
+                let exact = S::from_vec3(transform.scale).to_vec3() == latest;
+                let forced = keyframe.is_some_and(|k| 
+                    k.is_forced(snap.tick(), tick)
+                );
+
+                if exact
+                || (!forced
+                && latest.distance_squared(transform.scale) 
+                <= config.scale_threshold_sq()) {
+                    cache.cache();
+                    continue;
+                }
+            }
+            None => warn!("no snapshots found")
+        }
+
+        *s = S::from_vec3(transform.scale);
+        cache.cache();
+        debug!("updated scale: {}", transform.scale);
+    } 
+}
+
+pub(crate) fn apply_network_scale_system<S>(
+    mut query: Query<(
+        &mut Transform,
+        &S,
+        &mut ComponentCache<S>,
+    ), 
+        Without<Owning>
+    >,
+    render_delay: Res<RenderDelay>,
+    timeline: Res<NetworkTimeline>,
+    config: Res<InterpolationConfig>,
+    time: Res<Time<Real>>
+) 
+where S: NetworkScale {
+    let Some(render_tick) = timeline.tick_at(
+        time.elapsed_seconds_f64() - render_delay.delay(), 
+        &config
+    ) else {
+        return;
+    };
+
+    for (mut transform, net_scale, mut cache) in query.iter_mut() {
+        let Some((from, to, per)) = cache.sample_at_tick(render_tick) else {
+            transform.scale = net_scale.to_vec3();
+            continue;
+        };
+
+        transform.scale = from.component()
+        .interpolate(
+            to.component(), 
+            per.clamp(0.0, 1.0)
+        );
+
+        let consumed = cache.consumed_before_tick(render_tick);
+        if consumed > 0 {
+            cache.cache_n(consumed);
+        }
+    }
+}
+
+pub(crate) fn apply_transform_network_system<N>(
+    mut query: Query<(
+        &Transform, 
+        &mut N, 
+        &mut ComponentCache<N>,
+        Option<&KeyframeReplication>
+    ), 
+        Or<(Changed<Transform>, With<KeyframeReplication>)>
+    >,
+    config: Res<ReplicationConfig>,
+    axis: Res<TransformAxis>,
+    server_tick: Res<ServerTick>
+)
+where N: NetworkTransform {
+    let tick = server_tick.get();
+    for (transform, mut n, mut cache, keyframe) in query.iter_mut() {
+        match cache.latest_snapshot() {
+            Some(s) => {
+                // compare as written, quantized types can not be compared directly
+                let mut latest = Transform::IDENTITY;
+                s.component().write(&mut latest, &axis);
+                let mut current = Transform::IDENTITY;
+                N::from_transform(&transform, &axis).write(&mut current, &axis);
+                let exact = current == latest;
+                let forced = keyframe.is_some_and(|k| 
+                    k.is_forced(s.tick(), tick)
+                );
+
+                if exact
+                || (!forced
+                && !s.component()
+                .exceeds_threshold(&transform, &config, &axis)) {
+                    cache.cache();
+                    continue;
+                }
+            }
+            None => warn!("no snapshots found")
+        }
+
+        *n = N::from_transform(&transform, &axis);
+        cache.cache();
+        debug!("updated transform: {:?}", transform);
+    } 
+}
+
+pub(crate) fn apply_network_transform_system<N>(
+    mut query: Query<(
+        &mut Transform,
+        &N,
+        &mut ComponentCache<N>,
+    ), 
+        Without<Owning>
+    >,
+    axis: Res<TransformAxis>,
+    render_delay: Res<RenderDelay>,
+    timeline: Res<NetworkTimeline>,
+    config: Res<InterpolationConfig>,
+    time: Res<Time<Real>>
+) 
+where N: NetworkTransform {
+    let Some(render_tick) = timeline.tick_at(
+        time.elapsed_seconds_f64() - render_delay.delay(), 
+        &config
+    ) else {
+        return;
+    };
+
+    for (mut transform, net_transform, mut cache) in query.iter_mut() {
+        let Some((from, to, per)) = cache.sample_at_tick(render_tick) else {
+            net_transform.write(&mut transform, &axis);
+            continue;
+        };
+
+        from.component()
+        .write_interpolated(
+            to.component(), 
+            per.clamp(0.0, 1.0), 
+            &mut transform, 
+            &axis
+        );
+
+        let consumed = cache.consumed_before_tick(render_tick);
+        if consumed > 0 {
+            cache.cache_n(consumed);
+        }
+    }
+}
+
 pub(crate) fn apply_network_translation_system<T>(
     mut query: Query<(
         &mut Transform,
@@ -98,37 +397,75 @@ pub(crate) fn apply_network_translation_system<T>(
         Without<Owning>
     >,
     axis: Res<TransformAxis>,
-    config: Res<InterpolationConfig>
+    render_delay: Res<RenderDelay>,
+    timeline: Res<NetworkTimeline>,
+    config: Res<InterpolationConfig>,
+    time: Res<Time<Real>>
 ) 
 where T: NetworkTranslation {
+    // snapshots are placed by server tick, not by arrival
+    let Some(render_tick) = timeline.tick_at(
+        time.elapsed_seconds_f64() - render_delay.delay(), 
+        &config
+    ) else {
+        return;
+    };
+
     for (mut transform, net_trans, mut cache) in query.iter_mut() {
-        let (back_0, back_1) = match cache.frontier_back_pair() {
-            Some(back) => (back.0.component(), back.1.component()),
-            None => {
-                transform.translation = net_trans.to_vec3(axis.translation);
-                continue;
-            }
+        let Some((from, to, per)) = cache.sample_at_tick(render_tick) else {
+            transform.translation = net_trans.to_vec3(axis.translation);
+            continue;
         };
 
-        match cache.elapsed_per_network_tick(config.network_tick_delta) {
-            Ok(p) => {
-                let interpolated = back_1.interpolate(
-                    &back_0, 
-                    p.min(1.0), 
-                    axis.translation
-                );
-                transform.translation = interpolated;
-            }
-            Err(e) => {
-                error!("error on translation interpolation: {e}");
-                transform.translation = net_trans.to_vec3(axis.translation);
+        transform.translation = from.component()
+        .interpolate(
+            to.component(), 
+            per.clamp(0.0, 1.0), 
+            axis.translation
+        );
+
+        let consumed = cache.consumed_before_tick(render_tick);
+        if consumed > 0 {
+            cache.cache_n(consumed);
+        }
+    }
+}
+
+pub(crate) fn warm_rotation_system<R>(
+    mut query: Query<(
+        &mut Transform,
+        &R,
+        &mut ComponentCache<R>,
+        &ConfirmHistory
+    ),
+        (Added<ComponentCache<R>>, Without<Owning>)
+    >,
+    axis: Res<TransformAxis>,
+    clock: Res<NetworkClock>
+)
+where R: NetworkRotation {
+    for (mut transform, net_rot, mut cache, confirmed) in query.iter_mut() {
+        if cache.frontier_len() == 0 && cache.cache_len() == 0 {
+            let tick = confirmed.last_tick().get();
+            if let Err(e) = cache.insert(*net_rot, tick, clock.now()) {
+                warn!("could not warm rotation cache: {e}");
             }
-        };
+        }
+        transform.rotation = net_rot.to_quat(axis.rotation);
+    }
+}
 
-        const REQUIRED: usize = 2;
+pub(crate) fn teleport_rotation_system<R>(
+    mut query: Query<
+        &mut ComponentCache<R>, 
+        (Changed<NetworkTeleport>, Without<Owning>)
+    >
+)
+where R: NetworkRotation {
+    for mut cache in query.iter_mut() {
         let frontier_len = cache.frontier_len();
-        if frontier_len > REQUIRED {
-            cache.cache_n(frontier_len - REQUIRED);
+        if frontier_len > 1 {
+            cache.cache_n(frontier_len - 1);
         }
     }
 }
@@ -142,37 +479,36 @@ pub(crate) fn apply_network_rotation_system<R>(
         Without<Owning>
     >,
     axis: Res<TransformAxis>,
-    config: Res<InterpolationConfig>
-)
+    render_delay: Res<RenderDelay>,
+    timeline: Res<NetworkTimeline>,
+    config: Res<InterpolationConfig>,
+    time: Res<Time<Real>>
+) 
 where R: NetworkRotation {
+    // snapshots are placed by server tick, not by arrival
+    let Some(render_tick) = timeline.tick_at(
+        time.elapsed_seconds_f64() - render_delay.delay(), 
+        &config
+    ) else {
+        return;
+    };
+
     for (mut transform, net_rot, mut cache) in query.iter_mut() {
-        let (back_0, back_1) = match cache.frontier_back_pair() {
-            Some(back) => (back.0.component(), back.1.component()),
-            None => {
-                transform.rotation = net_rot.to_quat(axis.rotation);
-                continue;
-            }
+        let Some((from, to, per)) = cache.sample_at_tick(render_tick) else {
+            transform.rotation = net_rot.to_quat(axis.rotation);
+            continue;
         };
 
-        match cache.elapsed_per_network_tick(config.network_tick_delta) {
-            Ok(p) => {
-                let interpolated = back_1.interpolate(
-                    &back_0, 
-                    p.min(1.0), 
-                    axis.rotation
-                );
-                transform.rotation = interpolated;
-            }
-            Err(e) => {
-                error!("error on rotation interpolation: {e}");
-                transform.rotation = net_rot.to_quat(axis.rotation);
-            }
-        };
+        transform.rotation = from.component()
+        .interpolate(
+            to.component(), 
+            per.clamp(0.0, 1.0), 
+            axis.rotation
+        );
 
-        const REQUIRED: usize = 2;
-        let frontier_len = cache.frontier_len();
-        if frontier_len > REQUIRED {
-            cache.cache_n(frontier_len - REQUIRED);
+        let consumed = cache.consumed_before_tick(render_tick);
+        if consumed > 0 {
+            cache.cache_n(consumed);
         }
     }
 }
@@ -180,18 +516,21 @@ where R: NetworkRotation {
 pub(crate) fn correct_translation_error_system<T, E>(
     mut query: Query<(
         &NetworkEntity,
+        &Transform,
         &mut ComponentCache<T>, 
         &mut PredioctionError<T>,
         &mut EventCache<E>
     )>,
     axis: Res<TransformAxis>,
     config: Res<PredictionConfig>,
+    server_tick: Res<ServerTick>,
     mut trans_force_repl: EventWriter<CorrectTranslation<T>>
 )
 where 
 T: NetworkTranslation, 
 E: NetworkMovement {
     for (net_e,
+        transform,
         mut trans_cache, 
         mut trans_pred_err,
         mut movements
@@ -236,10 +575,12 @@ E: NetworkMovement {
         if trans_err > config.translation_threshold_sq() {
             trans_pred_err.increment_count();
             if trans_pred_err.get_count() > config.force_replicate_error_count {
-                // frontier is not empty
-                let last_idx = movements.frontier_back()
-                .unwrap()
-                .index();
+                // current state reflects movements already simulated,
+                // not frontier that is still waiting for simulation
+                let Some(last_idx) = movements.last_processed_index() else {
+                    warn!("no movement is processed yet, skipping force replication");
+                    continue;
+                };
 
                 warn!(
                     "sending translation force replication for: {:?}", 
@@ -248,7 +589,11 @@ E: NetworkMovement {
 
                 trans_force_repl.send(CorrectTranslation { 
                     mode: SendMode::Direct(net_e.client_id()), 
-                    event: ForceReplicateTranslation::new(last_idx)
+                    event: ForceReplicateTranslation::new(
+                        T::from_vec3(transform.translation, axis.translation),
+                        server_tick.get(),
+                        last_idx
+                    )
                 });
 
                 trans_pred_err.reset_count();
@@ -262,12 +607,14 @@ E: NetworkMovement {
 pub(crate) fn correct_rotation_error_system<R, E>(
     mut query: Query<(
         &NetworkEntity,
+        &Transform,
         &mut ComponentCache<R>, 
         &mut PredioctionError<R>,
         &mut EventCache<E>
     )>,
     axis: Res<TransformAxis>,
     config: Res<PredictionConfig>,
+    server_tick: Res<ServerTick>,
     mut rot_force_repl: EventWriter<CorrectRotation<R>>
 )
 where 
@@ -275,6 +622,7 @@ R: NetworkRotation,
 E: NetworkMovement {
     for (
         net_e,
+        transform,
         mut rot_cache, 
         mut rot_pred_err, 
         mut movements
@@ -325,10 +673,12 @@ E: NetworkMovement {
         if rot_err > config.rotation_threshold {
             rot_pred_err.increment_count();
             if rot_pred_err.get_count() > config.force_replicate_error_count {
-                // frontier is not empty
-                let last_idx = movements.frontier_back()
-                .unwrap()
-                .index();
+                // current state reflects movements already simulated,
+                // not frontier that is still waiting for simulation
+                let Some(last_idx) = movements.last_processed_index() else {
+                    warn!("no movement is processed yet, skipping force replication");
+                    continue;
+                };
                 
                 warn!(
                     "sending rotation force replication for: {:?}", 
@@ -337,7 +687,11 @@ E: NetworkMovement {
 
                 rot_force_repl.send(ToClients{
                     mode: SendMode::Direct(net_e.client_id()),
-                    event: ForceReplicateRotation::new(last_idx)
+                    event: ForceReplicateRotation::new(
+                        R::from_quat(transform.rotation, axis.rotation),
+                        server_tick.get(),
+                        last_idx
+                    )
                 });
 
                 rot_pred_err.reset_count();    
@@ -349,168 +703,1007 @@ E: NetworkMovement {
 }
 
 pub(crate) fn handle_correct_translation<T, E>(
-    mut query: Query<(
-        &mut Transform,
-        &T,
-        &mut EventCache<E>
-    ), 
-        With<Owning>
-    >,
+    mut query: Query<
+        &mut Reconciliation, (
+        With<Owning>,
+        With<EventCache<E>>
+    )>,
     mut force_replication: EventReader<ForceReplicateTranslation<T>>,
     axis: Res<TransformAxis>
 )
 where 
 T: NetworkTranslation,
 E: NetworkMovement {
-    let Ok((
-        mut transform,
-        net_trans, 
-        mut movements
-    )) = query.get_single_mut() else {
+    let Ok(mut reconciliation) = query.get_single_mut() else {
         return;
     };
     
-    let mut sort = false;
-
     for e in force_replication.read() {
         warn!(
-            "force replicate translation, last index: {}",
-            e.last_index
+            "force replicate translation, last index: {} at tick: {}",
+            e.last_index,
+            e.tick
         );
 
-        let next_idx = e.last_index() + 1;
-        
-        if movements.frontier_len() > 0 {
-            // frontier is not empty
-            let frontier_next = movements.frontier_front()
-            .unwrap()
-            .index();
-            info!("frontier next index: {frontier_next}");
-            
-            if frontier_next <= next_idx {
-                let skip = next_idx - frontier_next;
-                if skip > 0 {
-                    movements.cache_n(skip);
-                    info!("skipping {skip} events");
-                }
-
-                transform.translation = net_trans.to_vec3(axis.translation);
-                continue;
-            }
-        }
-        
-        if movements.cache_len() == 0 {
-            transform.translation = net_trans.to_vec3(axis.translation);
-            continue;
-        }
-
-        let mut resend = vec![];
-        for m in movements.cache_ref()
-        .iter()
-        .rev() {
-            if m.index() < next_idx {
-                break;
-            }
-
-            resend.push(m.clone());
-        }
-
-        if resend.len() > 0 {
-            sort = true;
-            info!("{} events were resent", resend.len());
-        }
-
-        for m in resend {
-            movements.insert_unchecked(m);
-        }
-
-        transform.translation = net_trans.to_vec3(axis.translation);    
-    }
-
-    if sort {
-        movements.sort_frontier_by_index();
+        // correct against the state carried by event,
+        // replicated component can be from another tick
+        reconciliation.request_translation(
+            e.last_index(), 
+            e.translation()
+            .to_vec3(axis.translation)
+        );
     }
 }
 
 pub(crate) fn handle_correct_rotation<R, E>(
-    mut query: Query<(
-        &mut Transform, 
-        &R,
-        &mut EventCache<E>
-    ), 
-        With<Owning>
-    >,
+    mut query: Query<
+        &mut Reconciliation, (
+        With<Owning>,
+        With<EventCache<E>>
+    )>,
     mut force_replication: EventReader<ForceReplicateRotation<R>>,
     axis: Res<TransformAxis>
 )
 where 
 R: NetworkRotation,
 E: NetworkMovement {
-    let Ok((
-        mut transform, 
-        net_rot,
-        mut movements
-    )) = query.get_single_mut() else {
+    let Ok(mut reconciliation) = query.get_single_mut() else {
         return;
     };
 
-    let mut sort = false;
-
     for e in force_replication.read() {
         warn!(
-            "force replicate rotation, last index: {}",
-            e.last_index
+            "force replicate rotation, last index: {} at tick: {}",
+            e.last_index,
+            e.tick
         );
 
-        let next_idx = e.last_index() + 1;
-        
-        if movements.frontier_len() > 0 {
-            // frontier is not empty
-            let frontier_next = movements.frontier_front()
-            .unwrap()
-            .index();
-            info!("frontier next index: {frontier_next}");
-            if frontier_next <= next_idx {
-                let skip = next_idx - frontier_next;
-                if skip > 0 {
-                    movements.cache_n(skip);
-                    info!("skipping {skip} events");
-                }
+        // correct against the state carried by event,
+        // replicated component can be from another tick
+        reconciliation.request_rotation(
+            e.last_index(), 
+            e.rotation()
+            .to_quat(axis.rotation)
+        );
+    }
+}
 
-                transform.rotation = net_rot.to_quat(axis.rotation);
-                continue;
-            }
-        } 
-        
-        if movements.cache_len() == 0 {
-            transform.rotation = net_rot.to_quat(axis.rotation);
-            continue;
+pub(crate) fn insert_reconciliation_system<E>(
+    mut commands: Commands,
+    query: Query<
+        Entity, (
+        With<Owning>,
+        With<EventCache<E>>,
+        Without<Reconciliation>
+    )>
+)
+where E: NetworkMovement {
+    for e in query.iter() {
+        commands.entity(e)
+        .insert(Reconciliation::default());
+    }
+}
+
+pub(crate) fn reconcile_system<E>(
+    mut query: Query<(
+        &mut Transform,
+        &mut EventCache<E>,
+        &mut Reconciliation,
+        Option<&InputAck<E>>,
+        Option<&mut PredictionSmoothing>
+    ), 
+        With<Owning>
+    >,
+    axis: Res<TransformAxis>
+)
+where E: NetworkMovement {
+    for (
+        mut transform, 
+        mut movements, 
+        mut reconciliation,
+        input_ack,
+        smoothing
+    ) in query.iter_mut() {
+        // ack is sent after correction in same server frame and often overtakes it,
+        // so acknowledged movements are kept until pending correction is applied
+        let ack_idx = input_ack.and_then(|ack| ack.index());
+        let Some(last_index) = reconciliation.last_index() else {
+            if let Some(ack_idx) = ack_idx {
+                movements.discard_acknowledged(ack_idx);
+            }
+            continue;
+        };
+
+        if let Some(mut s) = smoothing {
+            s.begin(&transform);
         }
 
-        let mut resend = vec![];
-        for m in movements.cache_ref()
-        .iter()
-        .rev() {
-            if m.index() < next_idx {
-                break;
+        // every unacknowledged movement goes back to frontier,
+        // simulated ones are replayed from rewound state before render
+        let replay = movements.rewind(last_index);
+        reconciliation.set_replay(replay);
+        info!("rewound to index: {last_index}, {replay} events will be replayed");
+
+        // state which is not corrected is rewound to client's own prediction
+        // that is reported with the first unacknowledged movement
+        let first_unacked = movements.frontier_front()
+        .map(|s| s.event());
+
+        match reconciliation.translation() {
+            Some(t) => transform.translation = t,
+            None => if let Some(m) = first_unacked {
+                transform.translation = m.current_translation(axis.translation);
             }
+        }
 
-            resend.push(m.clone());
+        match reconciliation.rotation() {
+            Some(r) => transform.rotation = r,
+            None => if let Some(m) = first_unacked {
+                transform.rotation = m.current_rotation(axis.rotation);
+            }
         }
 
-        if resend.len() > 0 {
-            sort = true;
-            info!("{} events were resent", resend.len());
+        reconciliation.clear();
+
+        // rewound movements are in frontier now, only older ones are discarded
+        if let Some(ack_idx) = ack_idx {
+            movements.discard_acknowledged(ack_idx.min(last_index));
+        }
+    }
+}
+
+pub(crate) fn replay_movement_system<M, E>(
+    mut query: Query<(
+        &mut EventCache<E>,
+        &mut Reconciliation,
+        M::State
+    ),
+        With<Owning>
+    >,
+    model: Res<M>,
+    dilation: Option<Res<TimeDilation>>,
+    time: Res<Time<Fixed>>
+)
+where 
+M: MovementModel<E>,
+E: NetworkMovement {
+    let delta = match dilation.as_ref()
+    .and_then(|d| d.nominal_timestep()) {
+        Some(t) => t as f32,
+        None => time.delta_seconds()
+    };
+
+    for (mut movements, mut reconciliation, mut state) in query.iter_mut() {
+        let replay = reconciliation.take_replay()
+        .min(movements.frontier_len());
+        if replay == 0 {
+            continue;
         }
 
-        for m in resend {
-            movements.insert_unchecked(m);
+        // one step per movement as server did, 
+        // movements not simulated yet are left for fixed update
+        for snap in movements.frontier_ref()[..replay].iter() {
+            model.replay(&mut state, snap.event(), delta);
         }
+
+        movements.cache_n(replay);
+        debug!("replayed {replay} movements");
+    }
+}
+
+pub(crate) fn resolve_smoothing_system(
+    mut query: Query<
+        (&Transform, &mut PredictionSmoothing), 
+        With<Owning>
+    >,
+    config: Res<SmoothingConfig>
+) {
+    for (transform, mut smoothing) in query.iter_mut() {
+        if !smoothing.is_pending() {
+            continue;
+        }
+
+        // replayed state is ready here
+        smoothing.resolve(&transform, &config);
+        debug!(
+            "smoothing offset translation: {} rotation: {}",
+            smoothing.translation_offset(),
+            smoothing.rotation_offset()
+        );
+    }
+}
+
+pub(crate) fn apply_smoothing_system(
+    mut query: Query<
+        (&Transform, &mut PredictionSmoothing, &Children), 
+        With<Owning>
+    >,
+    mut visuals: Query<
+        (&mut Transform, &SmoothingVisual), 
+        Without<Owning>
+    >,
+    config: Res<SmoothingConfig>,
+    time: Res<Time<Real>>
+) {
+    for (transform, mut smoothing, children) in query.iter_mut() {
+        // decays per rendered frame, not per fixed tick
+        smoothing.decay(time.delta_seconds(), &config);
         
-        transform.rotation = net_rot.to_quat(axis.rotation);
+        // only visual children have offset,
+        // simulation keeps authoritative transform
+        let offset = smoothing.local_offset(transform);
+        for child in children.iter() {
+            let Ok((mut visual_transform, visual)) = visuals.get_mut(*child) else {
+                continue;
+            };
+
+            *visual_transform = offset.mul_transform(visual.base);
+        }
+    }
+}
+
+pub(crate) fn insert_input_ack_system<E>(
+    mut commands: Commands,
+    query: Query<
+        Entity, (
+        With<EventCache<E>>,
+        Without<InputAck<E>>
+    )>
+)
+where E: NetworkMovement {
+    for e in query.iter() {
+        commands.entity(e)
+        .insert(InputAck::<E>::default());
+    }
+}
+
+pub(crate) fn server_input_ack_system<E>(
+    mut query: Query<(
+        &NetworkEntity,
+        &EventCache<E>,
+        &mut InputAck<E>
+    )>,
+    server_tick: Res<ServerTick>,
+    mut acks: EventWriter<SendInputAck<E>>
+)
+where E: NetworkMovement {
+    let tick = server_tick.get();
+    for (net_e, movements, mut ack) in query.iter_mut() {
+        if let Some(idx) = movements.last_processed_index() {
+            ack.try_set(idx, tick);
+        }
+
+        let Some(idx) = ack.index() else {
+            continue;
+        };
+
+        // sent every tick until newer one replaces it,
+        // unreliable channel could drop the latest one
+        acks.send(SendInputAck { 
+            mode: SendMode::Direct(net_e.client_id()), 
+            event: AcknowledgeInput::new(idx, ack.tick())
+        });
+        trace!("acknowledged index: {idx} for: {:?}", net_e.client_id());
+    }
+}
+
+pub(crate) fn client_input_ack_system<E>(
+    mut query: Query<&mut InputAck<E>, With<Owning>>,
+    mut acks: EventReader<AcknowledgeInput<E>>,
+    mut redundant_buffer: Option<ResMut<RedundantEventBuffer<E>>>
+)
+where E: NetworkMovement {
+    let Ok(mut ack) = query.get_single_mut() else {
+        return;
+    };
+
+    for e in acks.read() {
+        if !ack.try_set(e.index(), e.tick) {
+            continue;
+        }
+
+        // movements are discarded by reconciliation, 
+        // pending correction still needs them for replay
+        if let Some(ref mut buffer) = redundant_buffer {
+            buffer.discard_acknowledged(e.index());
+        }
+        trace!("acknowledged index: {} at tick: {}", e.index(), e.tick);
+    }
+}
+
+pub(crate) fn apply_local_predicted_system<C, E>(
+    mut query: Query<
+        (&C::Local, &mut C), 
+        Changed<C::Local>
+    >
+)
+where 
+C: NetworkPredicted<E>,
+E: NetworkMovement {
+    for (local, mut c) in query.iter_mut() {
+        let updated = C::from_local(local);
+        if !c.exceeds_threshold(&updated) {
+            continue;
+        }
+
+        *c = updated;
+    }
+}
+
+pub(crate) fn correct_predicted_error_system<C, E>(
+    mut query: Query<(
+        &NetworkEntity,
+        &C::Local,
+        &mut ComponentCache<C>,
+        &mut PredioctionError<C>,
+        &mut EventCache<E>
+    )>,
+    config: Res<PredictionConfig>,
+    server_tick: Res<ServerTick>,
+    mut force_repl: EventWriter<CorrectPredicted<C>>
+)
+where 
+C: NetworkPredicted<E>,
+E: NetworkMovement {
+    for (
+        net_e,
+        local,
+        mut cache,
+        mut pred_err,
+        mut movements
+    ) in query.iter_mut() {
+        cache.cache();
+
+        if movements.frontier_len() == 0 {
+            continue;
+        }
+
+        movements.sort_frontier_by_index();
+
+        // frontier is not empty
+        let frontier_snap = movements.frontier_front()
+        .unwrap();
+        let frontier_tick = frontier_snap.sent_tick();
+
+        let Some(client_c) = C::from_movement(frontier_snap.event()) else {
+            continue;
+        };
+
+        let found_snap = match cache.find_at_tick(frontier_tick) {
+            Some(s) => s,
+            None => {
+                error!("could not find snapshot for tick: {frontier_tick}, skipping");
+                continue;
+            }
+        };
+        debug!(
+            "found snapshot at: {} for event's tick: {}",
+            found_snap.tick(),
+            frontier_tick
+        );
+
+        if found_snap.component()
+        .exceeds_threshold(&client_c) {
+            pred_err.increment_count();
+            if pred_err.get_count() > config.force_replicate_error_count {
+                let Some(last_idx) = movements.last_processed_index() else {
+                    warn!("no movement is processed yet, skipping force replication");
+                    continue;
+                };
+
+                warn!(
+                    "sending predicted component force replication for: {:?}", 
+                    net_e.client_id()
+                );
+
+                force_repl.send(CorrectPredicted { 
+                    mode: SendMode::Direct(net_e.client_id()), 
+                    event: ForceReplicatePredicted::new(
+                        C::from_local(local),
+                        server_tick.get(),
+                        last_idx
+                    )
+                });
+
+                pred_err.reset_count();
+            }
+        } else {
+            pred_err.reset_count();
+        }
+    }
+}
+
+pub(crate) fn apply_network_predicted_system<C, E>(
+    mut query: Query<(
+        &mut C::Local,
+        &C,
+        &mut ComponentCache<C>
+    ), (
+        Without<Owning>,
+        Changed<C>
+    )>
+)
+where 
+C: NetworkPredicted<E>,
+E: NetworkMovement {
+    for (mut local, c, mut cache) in query.iter_mut() {
+        c.apply_local(&mut local);
+        cache.cache();
+    }
+}
+
+pub(crate) fn cache_predicted_system<C, E>(
+    mut query: Query<
+        &mut ComponentCache<C>, 
+        (With<Owning>, Changed<ComponentCache<C>>)
+    >
+)
+where 
+C: NetworkPredicted<E>,
+E: NetworkMovement {
+    for mut cache in query.iter_mut() {
+        cache.cache();
+    }
+}
+
+pub(crate) fn handle_correct_predicted<C, E>(
+    mut query: Query<(
+        &mut C::Local,
+        &mut Reconciliation
+    ), (
+        With<Owning>,
+        With<EventCache<E>>
+    )>,
+    mut force_replication: EventReader<ForceReplicatePredicted<C>>
+)
+where 
+C: NetworkPredicted<E>,
+E: NetworkMovement {
+    let Ok((mut local, mut reconciliation)) = query.get_single_mut() else {
+        return;
+    };
+
+    for e in force_replication.read() {
+        warn!(
+            "force replicate predicted component, last index: {} at tick: {}",
+            e.last_index,
+            e.tick
+        );
+
+        e.component()
+        .apply_local(&mut local);
+        reconciliation.request_component::<C>(e.last_index());
+    }
+}
+
+pub(crate) fn rewind_predicted_system<C, E>(
+    mut query: Query<(
+        &mut C::Local,
+        &EventCache<E>,
+        &Reconciliation
+    ), 
+        With<Owning>
+    >
+)
+where 
+C: NetworkPredicted<E>,
+E: NetworkMovement {
+    for (mut local, movements, reconciliation) in query.iter_mut() {
+        let Some(last_index) = reconciliation.last_index() else {
+            continue;
+        };
+
+        if reconciliation.is_corrected::<C>() {
+            continue;
+        }
+
+        // not corrected state is rewound to client's own prediction
+        // that is reported with the first unacknowledged movement
+        let Some(first_unacked) = movements.first_after(last_index) else {
+            continue;
+        };
+
+        if let Some(c) = C::from_movement(first_unacked.event()) {
+            c.apply_local(&mut local);
+        }
     }
+}
 
-    if sort {
+pub(crate) fn movement_model_system<M, E>(
+    mut query: Query<(
+        &mut EventCache<E>, 
+        Option<&mut InputQueue<E>>,
+        M::State
+    )>,
+    model: Res<M>,
+    queue_config: Option<Res<InputQueueConfig>>,
+    dilation: Option<Res<TimeDilation>>,
+    time: Res<Time<Fixed>>
+)
+where 
+M: MovementModel<E>,
+E: NetworkMovement {
+    // dilated timestep only changes input rate, 
+    // simulation keeps same delta as server
+    let delta = match dilation.as_ref()
+    .and_then(|d| d.nominal_timestep()) {
+        Some(t) => t as f32,
+        None => time.delta_seconds()
+    };
+    
+    for (mut movements, queue, mut state) in query.iter_mut() {
+        // server consumes exactly one input per tick with queue
+        if let (Some(mut queue), Some(config)) = (queue, queue_config.as_ref()) {
+            if let Some(movement) = queue.pop(&mut movements, config) {
+                model.step(&mut state, &movement, delta);
+            }
+            continue;
+        }
+
+        // client predicts one movement per tick as server consumes them
         movements.sort_frontier_by_index();
+        let Some(snap) = movements.frontier_front() else {
+            continue;
+        };
+
+        model.step(&mut state, snap.event(), delta);
+        movements.cache_n(1);
+    }
+}
+
+pub(crate) fn insert_translation_extrapolation_system<L>(
+    mut commands: Commands,
+    query: Query<Entity, (Added<L>, Without<Owning>)>
+)
+where L: NetworkLinearVelocity {
+    for e in query.iter() {
+        commands.entity(e)
+        .insert(TranslationExtrapolation::default());
+    }
+}
+
+pub(crate) fn extrapolate_translation_system<T, L>(
+    mut query: Query<(
+        &mut Transform,
+        &mut TranslationExtrapolation,
+        &ComponentCache<T>,
+        &L
+    ),
+        Without<Owning>
+    >,
+    axis: Res<TransformAxis>,
+    render_delay: Res<RenderDelay>,
+    timeline: Res<NetworkTimeline>,
+    interpolation_config: Res<InterpolationConfig>,
+    config: Res<ExtrapolationConfig>,
+    time: Res<Time<Real>>
+)
+where 
+T: NetworkTranslation,
+L: NetworkLinearVelocity {
+    let Some(render_tick) = timeline.tick_at(
+        time.elapsed_seconds_f64() - render_delay.delay(), 
+        &interpolation_config
+    ) else {
+        return;
+    };
+    let ratio = config.blend_ratio(time.delta_seconds());
+
+    for (mut transform, mut extrapolation, cache, net_linvel) in query.iter_mut() {
+        let base = transform.translation;
+        let Some(back) = cache.frontier_back() else {
+            continue;
+        };
+
+        // frontier ran dry, render tick is beyond latest snapshot
+        let ahead = (render_tick - back.tick() as f64) 
+        * interpolation_config.network_tick_delta;
+        if ahead > 0.0 {
+            let t = (ahead as f32).min(config.max_time);
+            extrapolation.extrapolate(net_linvel.to_vec3(axis.translation), t);
+        } else {
+            extrapolation.blend(base, ratio);
+        }
+
+        transform.translation = extrapolation.render(base);
+    }
+}
+
+pub(crate) fn insert_rotation_extrapolation_system<A>(
+    mut commands: Commands,
+    query: Query<Entity, (Added<A>, Without<Owning>)>
+)
+where A: NetworkAngularVelocity {
+    for e in query.iter() {
+        commands.entity(e)
+        .insert(RotationExtrapolation::default());
+    }
+}
+
+pub(crate) fn extrapolate_rotation_system<R, A>(
+    mut query: Query<(
+        &mut Transform,
+        &mut RotationExtrapolation,
+        &ComponentCache<R>,
+        &A
+    ),
+        Without<Owning>
+    >,
+    axis: Res<TransformAxis>,
+    render_delay: Res<RenderDelay>,
+    timeline: Res<NetworkTimeline>,
+    interpolation_config: Res<InterpolationConfig>,
+    config: Res<ExtrapolationConfig>,
+    time: Res<Time<Real>>
+)
+where 
+R: NetworkRotation,
+A: NetworkAngularVelocity {
+    let Some(render_tick) = timeline.tick_at(
+        time.elapsed_seconds_f64() - render_delay.delay(), 
+        &interpolation_config
+    ) else {
+        return;
+    };
+    let ratio = config.blend_ratio(time.delta_seconds());
+
+    for (mut transform, mut extrapolation, cache, net_angvel) in query.iter_mut() {
+        let base = transform.rotation;
+        let Some(back) = cache.frontier_back() else {
+            continue;
+        };
+
+        let ahead = (render_tick - back.tick() as f64) 
+        * interpolation_config.network_tick_delta;
+        if ahead > 0.0 {
+            let t = (ahead as f32).min(config.max_time);
+            extrapolation.extrapolate(net_angvel.to_vec3(axis.rotation), t);
+        } else {
+            extrapolation.blend(base, ratio);
+        }
+
+        transform.rotation = extrapolation.render(base);
+    }
+}
+
+pub(crate) fn hermite_translation_system<T, L>(
+    mut query: Query<(
+        &mut Transform,
+        &ComponentCache<T>,
+        &mut ComponentCache<L>
+    ),
+        (With<HermiteInterpolation>, Without<Owning>)
+    >,
+    axis: Res<TransformAxis>,
+    render_delay: Res<RenderDelay>,
+    timeline: Res<NetworkTimeline>,
+    config: Res<InterpolationConfig>,
+    time: Res<Time<Real>>
+)
+where 
+T: NetworkTranslation,
+L: NetworkLinearVelocity {
+    let Some(render_tick) = timeline.tick_at(
+        time.elapsed_seconds_f64() - render_delay.delay(), 
+        &config
+    ) else {
+        return;
+    };
+
+    for (mut transform, trans_cache, mut vel_cache) in query.iter_mut() {
+        let Some((from, to, per)) = trans_cache.sample_at_tick(render_tick) else {
+            continue;
+        };
+
+        let span = to.tick().saturating_sub(from.tick()) as f64 
+        * config.network_tick_delta;
+        if span <= 0.0 {
+            continue;
+        }
+
+        let (Some(v0), Some(v1)) = (
+            vel_cache.snapshot_at_tick(from.tick()),
+            vel_cache.snapshot_at_tick(to.tick())
+        ) else {
+            continue;
+        };
+
+        transform.translation = hermite(
+            from.component().to_vec3(axis.translation), 
+            v0.component().to_vec3(axis.translation), 
+            to.component().to_vec3(axis.translation), 
+            v1.component().to_vec3(axis.translation), 
+            span as f32, 
+            per.clamp(0.0, 1.0)
+        );
+
+        let consumed = vel_cache.consumed_before_tick(render_tick);
+        if consumed > 0 {
+            vel_cache.cache_n(consumed);
+        }
+    }
+}
+
+pub(crate) fn insert_interpolate_system<C>(
+    mut commands: Commands,
+    query: Query<(
+        Entity,
+        &C,
+        &ConfirmHistory
+    ),
+        (Added<C>, Without<ComponentCache<C>>)
+    >,
+    cache_size: Res<InterpolateCacheSize<C>>,
+    clock: Res<NetworkClock>
+)
+where C: NetworkInterpolate {
+    for (e, c, confirmed) in query.iter() {
+        let cache = match ComponentCache::with_init(
+            c.clone(), 
+            confirmed.last_tick().get(), 
+            clock.now(), 
+            cache_size.cache_size
+        ) {
+            Ok(cache) => cache,
+            Err(e) => {
+                warn!("could not insert initial snapshot: {e}");
+                continue;
+            }
+        };
+
+        commands.entity(e)
+        .insert((cache, c.to_display()));
+    }
+}
+
+pub(crate) fn interpolate_display_system<C>(
+    mut query: Query<(
+        &mut C::Display,
+        &mut ComponentCache<C>
+    )>,
+    render_delay: Res<RenderDelay>,
+    timeline: Res<NetworkTimeline>,
+    config: Res<InterpolationConfig>,
+    time: Res<Time<Real>>
+)
+where C: NetworkInterpolate {
+    let Some(render_tick) = timeline.tick_at(
+        time.elapsed_seconds_f64() - render_delay.delay(), 
+        &config
+    ) else {
+        return;
+    };
+
+    for (mut display, mut cache) in query.iter_mut() {
+        let Some((from, to, per)) = cache.sample_at_tick(render_tick) else {
+            continue;
+        };
+
+        from.component()
+        .write_interpolated(
+            to.component(), 
+            per.clamp(0.0, 1.0), 
+            &mut display
+        );
+
+        let consumed = cache.consumed_before_tick(render_tick);
+        if consumed > 0 {
+            cache.cache_n(consumed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use serde::{Serialize, Deserialize};
+    use bevy::ecs::query::QueryItem;
+    use super::*;
+
+    type TestMovement = NetworkMovement2D;
+
+    fn ack_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<ServerTick>()
+        .add_event::<SendInputAck<TestMovement>>()
+        .add_systems(Update, 
+            server_input_ack_system::<TestMovement>
+            .run_if(resource_changed::<ServerTick>)
+        );
+
+        let mut movements = EventCache::<TestMovement>::with_capacity(8);
+        movements.skip_index();
+        app.world_mut().spawn((
+            NetworkEntity::new(ClientId::new(1)),
+            movements,
+            InputAck::<TestMovement>::default()
+        ));
+        app
+    }
+
+    fn drain_acks(app: &mut App) -> Vec<AcknowledgeInput<TestMovement>> {
+        app.world_mut()
+        .resource_mut::<Events<SendInputAck<TestMovement>>>()
+        .drain()
+        .map(|e| e.event)
+        .collect()
+    }
+
+    #[test]
+    fn dropped_input_ack_is_recovered() {
+        let mut app = ack_app();
+
+        app.update();
+        let sent = drain_acks(&mut app);
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].index(), 0);
+        // dropped on the way, client never sees it
+
+        // no new tick, nothing is sent
+        app.update();
+        assert!(drain_acks(&mut app).is_empty());
+
+        app.world_mut()
+        .resource_mut::<ServerTick>()
+        .increment();
+        app.update();
+        let resent = drain_acks(&mut app);
+        assert_eq!(resent.len(), 1);
+
+        let mut client_ack = InputAck::<TestMovement>::default();
+        assert!(client_ack.try_set(resent[0].index(), resent[0].tick));
+        assert!(client_ack.is_acknowledged(0));
+    }
+
+    #[derive(Component, Default)]
+    struct TestSpeed(f32);
+
+    #[derive(Component, Serialize, Deserialize, Clone, Copy, Default)]
+    struct TestPredicted(f32);
+
+    impl NetworkPredicted<NetworkMovement2_5D> for TestPredicted {
+        type Local = TestSpeed;
+
+        fn from_local(local: &TestSpeed) -> Self {
+            Self(local.0)
+        }
+
+        fn apply_local(&self, local: &mut TestSpeed) {
+            local.0 = self.0;
+        }
+
+        fn from_movement(movement: &NetworkMovement2_5D) -> Option<Self> {
+            Some(Self(movement.current_vertical_speed))
+        }
+
+        fn exceeds_threshold(&self, rhs: &Self) -> bool {
+            (self.0 - rhs.0).abs() > 0.5
+        }
+    }
+
+    #[derive(Resource, Clone)]
+    struct TestModel;
+
+    impl MovementModel<NetworkMovement2_5D> for TestModel {
+        type State = (&'static mut Transform, &'static mut TestSpeed);
+
+        fn step(
+            &self, 
+            state: &mut QueryItem<Self::State>, 
+            movement: &NetworkMovement2_5D, 
+            delta: f32
+        ) {
+            let (transform, speed) = state;
+            speed.0 += movement.linear_axis.y;
+            transform.translation.y += speed.0 * delta;
+        }
+    }
+
+    fn movement(index: u64, vertical_speed: f32) -> NetworkMovement2_5D {
+        NetworkMovement2_5D{
+            current_vertical_speed: vertical_speed,
+            linear_axis: Vec2::Y,
+            index,
+            tick: index as u32,
+            ..default()
+        }
+    }
+
+    fn prediction_error_app(client_speed: f32) -> App {
+        let mut app = App::new();
+        app.init_resource::<ServerTick>()
+        .insert_resource(PredictionConfig{
+            translation_threshold: 1.0,
+            rotation_threshold: 1.0,
+            force_replicate_error_count: 0
+        })
+        .add_event::<CorrectPredicted<TestPredicted>>()
+        .add_systems(Update, 
+            correct_predicted_error_system::<TestPredicted, NetworkMovement2_5D>
+        );
+
+        let mut movements = EventCache::with_capacity(8);
+        movements.skip_index();
+        movements.insert(movement(1, client_speed), 0.0)
+        .expect("could not insert movement");
+        app.world_mut().spawn((
+            NetworkEntity::new(ClientId::new(1)),
+            TestSpeed(2.0),
+            ComponentCache::with_init(TestPredicted(2.0), 0, 0.0, 8)
+            .expect("could not insert initial snapshot"),
+            PredioctionError::<TestPredicted>::default(),
+            movements
+        ));
+        app
+    }
+
+    fn drain_corrections(app: &mut App) -> Vec<ForceReplicatePredicted<TestPredicted>> {
+        app.world_mut()
+        .resource_mut::<Events<CorrectPredicted<TestPredicted>>>()
+        .drain()
+        .map(|e| e.event)
+        .collect()
+    }
+
+    #[test]
+    fn predicted_error_is_detected() {
+        let mut app = prediction_error_app(10.0);
+        app.update();
+        let corrections = drain_corrections(&mut app);
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(corrections[0].component.0, 2.0);
+        assert_eq!(corrections[0].last_index(), 0);
+
+        let mut app = prediction_error_app(2.2);
+        app.update();
+        assert!(drain_corrections(&mut app).is_empty());
+    }
+
+    #[test]
+    fn uncorrected_predicted_state_is_rewound_and_replayed() {
+        let mut fixed = Time::<Fixed>::from_seconds(0.5);
+        fixed.advance_by(Duration::from_millis(500));
+
+        let mut app = App::new();
+        app.insert_resource(fixed)
+        .insert_resource(TestModel)
+        .init_resource::<TransformAxis>()
+        .add_systems(Update, (
+            rewind_predicted_system::<TestPredicted, NetworkMovement2_5D>,
+            reconcile_system::<NetworkMovement2_5D>,
+            replay_movement_system::<TestModel, NetworkMovement2_5D>
+        ).chain());
+
+        // client reported speed 0, 1, 2 before each movement
+        let mut movements = EventCache::with_capacity(8);
+        for i in 0..3 {
+            movements.insert(movement(i, i as f32), 0.0)
+            .expect("could not insert movement");
+        }
+        movements.cache_n(3);
+
+        // server corrected only translation after first movement
+        let mut reconciliation = Reconciliation::default();
+        reconciliation.request_translation(0, Vec3::ZERO);
+
+        let e = app.world_mut().spawn((
+            Owning,
+            Transform::from_xyz(0.0, 100.0, 0.0),
+            TestSpeed(50.0),
+            movements,
+            reconciliation
+        )).id();
+        app.update();
+
+        // speed is rewound to 1 reported with movement 1,
+        // then movements 1 and 2 are simulated again
+        let world = app.world();
+        assert_eq!(world.get::<TestSpeed>(e).unwrap().0, 3.0);
+        let y = world.get::<Transform>(e).unwrap().translation.y;
+        assert!((y - 2.5).abs() < 1e-5, "y: {y}");
+        assert_eq!(
+            world.get::<EventCache<NetworkMovement2_5D>>(e).unwrap().frontier_len(), 
+            0
+        );
     }
 }
@@ -6,6 +6,15 @@ pub mod prediction;
 pub mod boot_system_set;
 pub mod player_start_line;
 pub mod latest_confirmed_tick;
+pub mod smoothing;
+pub mod input_ack;
+pub mod server_tick_estimate;
+pub mod network_clock;
+pub mod extrapolation;
+pub mod teleport;
+pub mod ghost;
+pub mod delta;
+pub mod keyframe;
 
 pub use network_entity::*;
 pub use network_event::*;
@@ -15,9 +24,21 @@ pub use prediction::*;
 pub use boot_system_set::*;
 pub use player_start_line::*;
 pub use latest_confirmed_tick::*;
+pub use smoothing::*;
+pub use input_ack::*;
+pub use server_tick_estimate::*;
+pub use network_clock::*;
+pub use extrapolation::*;
+pub use teleport::*;
+pub use ghost::*;
+pub use delta::*;
+pub use keyframe::*;
 
 use serde::{de::DeserializeOwned, Serialize};
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    ecs::query::{QueryData, QueryItem}
+};
 
 #[derive(Default, Clone, Copy)]
 pub enum TranslationAxis {
@@ -44,7 +65,10 @@ pub struct TransformAxis {
 #[derive(Resource, Clone)]
 pub struct ReplicationConfig {
     pub translation_threshold: f32,
-    pub rotation_threashold: f32
+    pub rotation_threashold: f32,
+    pub scale_threshold: f32,
+    /// translation change over this in one update is replicated as teleport
+    pub teleport_threshold: f32
 }
 
 impl ReplicationConfig {
@@ -52,6 +76,16 @@ impl ReplicationConfig {
     pub fn translation_threshold_sq(&self) -> f32 {
         self.translation_threshold * self.translation_threshold
     }
+
+    #[inline]
+    pub fn scale_threshold_sq(&self) -> f32 {
+        self.scale_threshold * self.scale_threshold
+    }
+
+    #[inline]
+    pub fn teleport_threshold_sq(&self) -> f32 {
+        self.teleport_threshold * self.teleport_threshold
+    }
 }
 
 pub trait NetworkTranslation: Component
@@ -60,6 +94,18 @@ pub trait NetworkTranslation: Component
     fn to_vec3(&self, axis: TranslationAxis) -> Vec3;
     fn interpolate(&self, rhs: &Self, per: f32, axis: TranslationAxis) 
     -> Vec3;
+
+    /// smallest representable change, 0.0 for full precision
+    #[inline]
+    fn quantization_step() -> f32 {
+        0.0
+    }
+
+    /// false when translation is clamped on encoding
+    #[inline]
+    fn is_in_range(_vec3: Vec3, _axis: TranslationAxis) -> bool {
+        true
+    }
 }
 
 pub trait NetworkRotation: Component
@@ -70,19 +116,97 @@ pub trait NetworkRotation: Component
     -> Quat;
 }
 
+pub trait NetworkScale: Component
++ Serialize + DeserializeOwned + Clone + Copy + Default {
+    fn from_vec3(vec3: Vec3) -> Self;
+    fn to_vec3(&self) -> Vec3;
+    fn interpolate(&self, rhs: &Self, per: f32) -> Vec3;
+}
+
+/// translation, rotation and optionally scale in one component,
+/// replicated and cached as one
+pub trait NetworkTransform: Component
++ Serialize + DeserializeOwned + Clone + Copy + Default {
+    fn from_transform(transform: &Transform, axis: &TransformAxis) -> Self;
+    fn write(&self, transform: &mut Transform, axis: &TransformAxis);
+    fn write_interpolated(
+        &self, 
+        rhs: &Self, 
+        per: f32, 
+        transform: &mut Transform, 
+        axis: &TransformAxis
+    );
+    fn exceeds_threshold(
+        &self, 
+        transform: &Transform, 
+        config: &ReplicationConfig, 
+        axis: &TransformAxis
+    ) -> bool;
+}
+
+/// any replicated component that is displayed smoothly on remote clients
+pub trait NetworkInterpolate: Component
++ Serialize + DeserializeOwned + Clone {
+    /// client side component written by interpolation
+    type Display: Component;
+    fn to_display(&self) -> Self::Display;
+    fn write_interpolated(&self, rhs: &Self, per: f32, display: &mut Self::Display);
+}
+
+/// component that can be sent as quantized offset from a baseline snapshot
+pub trait NetworkDelta: Component
++ Serialize + DeserializeOwned + Clone + Copy {
+    type Delta: Serialize + DeserializeOwned + Clone + Send + Sync + 'static;
+    /// None when offset does not fit, absolute value is sent instead
+    fn delta(&self, baseline: &Self, step: f32) -> Option<Self::Delta>;
+    fn apply_delta(baseline: &Self, delta: &Self::Delta, step: f32) -> Self;
+}
+
 pub trait NetworkLinearVelocity: Component
-+ Serialize + DeserializeOwned + Default {
++ Serialize + DeserializeOwned + Clone + Default {
     fn from_vec3(vec: Vec3, axis: TranslationAxis) -> Self;
     fn to_vec3(&self, axis: TranslationAxis) -> Vec3;
 }
 
 pub trait NetworkAngularVelocity: Component
-+ Serialize + DeserializeOwned + Default {
++ Serialize + DeserializeOwned + Clone + Default {
     fn from_vec3(vec: Vec3, axis: RotationAxis) -> Self;
     fn to_vec3(&self, axis: RotationAxis) -> Vec3;
 }
 
-pub trait NetworkMovement: NetworkEvent {
+/// named button declared by game, sent as one bit of movement event
+pub trait NetworkAction: Copy {
+    /// 0..16
+    fn bit(self) -> u8;
+}
+
+pub trait NetworkMovement: NetworkEvent + Default {
     fn current_translation(&self, axis: TranslationAxis) -> Vec3;
     fn current_rotation(&self, axis: RotationAxis) -> Quat;
 }
+
+pub trait NetworkPredicted<E: NetworkMovement>: Component
++ Serialize + DeserializeOwned + Clone {
+    type Local: Component;
+    fn from_local(local: &Self::Local) -> Self;
+    fn apply_local(&self, local: &mut Self::Local);
+    /// client's own prediction reported with movement, 
+    /// used to validate on server and to rewind when not corrected.
+    /// NetworkMovement2_5D carries vertical speed, 
+    /// other state needs a game defined movement
+    fn from_movement(movement: &E) -> Option<Self>;
+    fn exceeds_threshold(&self, rhs: &Self) -> bool;
+}
+
+pub trait MovementModel<E: NetworkMovement>: Resource + Clone {
+    type State: QueryData;
+    fn step(&self, state: &mut QueryItem<Self::State>, movement: &E, delta: f32);
+
+    /// steps unacknowledged movement again within one frame after correction,
+    /// physics does not run between replayed steps so override this
+    /// when step leaves integration to physics
+    #[inline]
+    fn replay(&self, state: &mut QueryItem<Self::State>, movement: &E, delta: f32) {
+        self.step(state, movement, delta);
+    }
+}
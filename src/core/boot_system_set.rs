@@ -15,9 +15,13 @@ pub enum ServerBootSet {
 pub enum ClientBootSet {
     UnboxReplication,
     ApplyReplication,
+    Reconcile,
     Cache
 }
 
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 
-pub struct BootsetMain;
\ No newline at end of file
+pub struct BootsetMain;
+
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct BootsetMovementModel;
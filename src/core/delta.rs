@@ -0,0 +1,462 @@
+use std::marker::PhantomData;
+use serde::{Serialize, Deserialize};
+use bevy::{
+    prelude::*,
+    ecs::entity::{MapEntities, EntityHashMap},
+    utils::HashMap
+};
+use bevy_replicon::{
+    prelude::*,
+    server::server_tick::ServerTick
+};
+use crate::cache::ComponentCache;
+use super::{NetworkDelta, NetworkClock};
+
+/// offset in quantization steps between value and baseline,
+/// both are snapped to the same grid so server and client agree on baseline
+#[inline]
+pub fn delta_steps(value: f32, baseline: f32, step: f32) -> Option<i16> {
+    if step <= 0.0 {
+        return None;
+    }
+
+    let d = (value / step).round() - (baseline / step).round();
+    if d < i16::MIN as f32 || d > i16::MAX as f32 {
+        return None;
+    }
+    Some(d as i16)
+}
+
+#[inline]
+pub fn apply_delta_steps(baseline: f32, delta: i16, step: f32) -> f32 {
+    ((baseline / step).round() + delta as f32) * step
+}
+
+#[derive(Resource)]
+pub struct DeltaConfig<C: NetworkDelta> {
+    /// quantization step of offsets
+    pub step: f32,
+    /// client side cache size, should cover round trip
+    pub cache_size: usize,
+    /// acknowledged snapshots older than this are not used as baseline
+    pub max_baseline_age: u32,
+    /// unacknowledged latest value is sent again after this server ticks,
+    /// should cover round trip
+    pub resend_interval: u32,
+    phantom: PhantomData<C>
+}
+
+impl<C: NetworkDelta> DeltaConfig<C> {
+    #[inline]
+    pub fn new(
+        step: f32, 
+        cache_size: usize, 
+        max_baseline_age: u32, 
+        resend_interval: u32
+    ) -> Self {
+        Self {
+            step,
+            cache_size,
+            max_baseline_age,
+            resend_interval,
+            phantom: PhantomData::<C>
+        }
+    }
+}
+
+impl<C: NetworkDelta> Clone for DeltaConfig<C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(
+            self.step, 
+            self.cache_size, 
+            self.max_baseline_age, 
+            self.resend_interval
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum DeltaValue<C: NetworkDelta> {
+    Absolute(C),
+    Delta {
+        baseline_tick: u32,
+        delta: C::Delta
+    }
+}
+
+#[derive(Event, Serialize, Deserialize, Clone)]
+pub struct DeltaReplication<C: NetworkDelta> {
+    pub entity: Entity,
+    pub tick: u32,
+    pub value: DeltaValue<C>
+}
+
+impl<C: NetworkDelta> MapEntities for DeltaReplication<C> {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity = entity_mapper.map_entity(self.entity);
+    }
+}
+
+pub type SendDeltaReplication<C> = ToClients<DeltaReplication<C>>;
+
+#[derive(Event, Serialize, Deserialize, Clone)]
+pub struct AcknowledgeDelta<C: NetworkDelta> {
+    pub entity: Entity,
+    pub tick: u32,
+    phantom: PhantomData<C>
+}
+
+impl<C: NetworkDelta> AcknowledgeDelta<C> {
+    #[inline]
+    pub fn new(entity: Entity, tick: u32) -> Self {
+        Self {
+            entity,
+            tick,
+            phantom: PhantomData::<C>
+        }
+    }
+}
+
+impl<C: NetworkDelta> MapEntities for AcknowledgeDelta<C> {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity = entity_mapper.map_entity(self.entity);
+    }
+}
+
+/// latest tick each client acknowledged and was sent per entity
+#[derive(Resource)]
+pub struct DeltaAcks<C: NetworkDelta> {
+    acks: HashMap<ClientId, EntityHashMap<u32>>,
+    sent: HashMap<ClientId, EntityHashMap<u32>>,
+    phantom: PhantomData<C>
+}
+
+impl<C: NetworkDelta> Default for DeltaAcks<C> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            acks: HashMap::new(),
+            sent: HashMap::new(),
+            phantom: PhantomData::<C>
+        }
+    }
+}
+
+impl<C: NetworkDelta> DeltaAcks<C> {
+    #[inline]
+    pub fn get(&self, client_id: ClientId, entity: Entity) -> Option<u32> {
+        self.acks.get(&client_id)?
+        .get(&entity)
+        .copied()
+    }
+
+    #[inline]
+    pub fn acknowledge(&mut self, client_id: ClientId, entity: Entity, tick: u32) {
+        let acked = self.acks.entry(client_id)
+        .or_default()
+        .entry(entity)
+        .or_insert(tick);
+        *acked = (*acked).max(tick);
+    }
+
+    #[inline]
+    pub fn sent_tick(&self, client_id: ClientId, entity: Entity) -> Option<u32> {
+        self.sent.get(&client_id)?
+        .get(&entity)
+        .copied()
+    }
+
+    #[inline]
+    pub fn mark_sent(&mut self, client_id: ClientId, entity: Entity, tick: u32) {
+        self.sent.entry(client_id)
+        .or_default()
+        .insert(entity, tick);
+    }
+
+    #[inline]
+    pub fn remove_entity(&mut self, entity: Entity) {
+        for acks in self.acks.values_mut() {
+            acks.remove(&entity);
+        }
+        for sent in self.sent.values_mut() {
+            sent.remove(&entity);
+        }
+    }
+
+    #[inline]
+    pub fn remove_client(&mut self, client_id: ClientId) {
+        self.acks.remove(&client_id);
+        self.sent.remove(&client_id);
+    }
+}
+
+pub(crate) fn server_delta_ack_system<C: NetworkDelta>(
+    mut acks: EventReader<FromClient<AcknowledgeDelta<C>>>,
+    mut delta_acks: ResMut<DeltaAcks<C>>,
+    mut removed: RemovedComponents<C>,
+    mut server_events: EventReader<ServerEvent>,
+    server_tick: Res<ServerTick>
+) {
+    for e in server_events.read() {
+        if let ServerEvent::ClientDisconnected { client_id, .. } = e {
+            delta_acks.remove_client(*client_id);
+        }
+    }
+
+    // despawned or no longer replicated
+    for e in removed.read() {
+        delta_acks.remove_entity(e);
+    }
+
+    let tick = server_tick.get();
+    for FromClient { client_id, event } in acks.read() {
+        if event.tick > tick {
+            warn!("client: {client_id:?} acknowledged future tick: {}", event.tick);
+            continue;
+        }
+
+        delta_acks.acknowledge(*client_id, event.entity, event.tick);
+    }
+}
+
+/// runs once per server tick after snapshot of the tick is cached,
+/// so snapshot at acknowledged tick is the value client has
+pub(crate) fn server_send_delta_system<C: NetworkDelta>(
+    query: Query<(Entity, Ref<C>, &ComponentCache<C>)>,
+    mut deltas: EventWriter<SendDeltaReplication<C>>,
+    mut delta_acks: ResMut<DeltaAcks<C>>,
+    connected_clients: Res<ConnectedClients>,
+    server_tick: Res<ServerTick>,
+    config: Res<DeltaConfig<C>>
+) {
+    let tick = server_tick.get();
+    for (e, c, cache) in query.iter() {
+        let Some(latest_tick) = cache.latest_snapshot()
+        .map(|s| s.tick()) else {
+            continue;
+        };
+
+        for client in connected_clients.iter() {
+            if !client.visibility().is_visible(e) {
+                continue;
+            }
+
+            let client_id = client.id();
+            let acked = delta_acks.get(client_id, e);
+            if !c.is_changed() {
+                if acked.is_some_and(|a| a >= latest_tick) {
+                    continue;
+                }
+
+                // unreliable channel, latest value is sent again 
+                // when it is not acknowledged within resend interval
+                let is_resend_due = delta_acks.sent_tick(client_id, e)
+                .map_or(true, |sent| tick.saturating_sub(sent) >= config.resend_interval);
+                if !is_resend_due {
+                    continue;
+                }
+            }
+
+            let value = acked
+            .filter(|acked| tick.saturating_sub(*acked) <= config.max_baseline_age)
+            .and_then(|acked| {
+                // the value client has at acked tick
+                let baseline = cache.snapshot_at_tick(acked)?;
+                let delta = c.delta(baseline.component(), config.step)?;
+                Some(DeltaValue::Delta{
+                    baseline_tick: acked,
+                    delta
+                })
+            })
+            .unwrap_or(DeltaValue::Absolute(*c));
+
+            deltas.send(ToClients {
+                mode: SendMode::Direct(client_id),
+                event: DeltaReplication{
+                    entity: e,
+                    tick,
+                    value
+                }
+            });
+            delta_acks.mark_sent(client_id, e, tick);
+        }
+    }
+}
+
+pub(crate) fn client_receive_delta_system<C: NetworkDelta>(
+    mut commands: Commands,
+    mut query: Query<(&mut C, &mut ComponentCache<C>)>,
+    mut deltas: EventReader<DeltaReplication<C>>,
+    mut acks: EventWriter<AcknowledgeDelta<C>>,
+    clock: Res<NetworkClock>,
+    config: Res<DeltaConfig<C>>
+) {
+    let now = clock.now();
+    for delta in deltas.read() {
+        let Ok((mut c, mut cache)) = query.get_mut(delta.entity) else {
+            // first value of this entity, only absolute one can be applied
+            let DeltaValue::Absolute(init) = delta.value else {
+                debug!("no baseline for entity: {:?}", delta.entity);
+                continue;
+            };
+
+            let Some(mut entity) = commands.get_entity(delta.entity) else {
+                warn!("entity: {:?} is not spawned", delta.entity);
+                continue;
+            };
+
+            match ComponentCache::with_init(init, delta.tick, now, config.cache_size) {
+                Ok(cache) => {
+                    entity.insert((init, cache));
+                    acks.send(AcknowledgeDelta::new(delta.entity, delta.tick));
+                }
+                Err(e) => warn!("could not insert initial snapshot: {e}")
+            }
+            continue;
+        };
+
+        // unreliable channel, older values could arrive late
+        if let Some(latest) = cache.latest_snapshot() {
+            if delta.tick <= latest.tick() {
+                trace!("discarding old delta tick: {}", delta.tick);
+                continue;
+            }
+        }
+
+        let value = match &delta.value {
+            DeltaValue::Absolute(value) => *value,
+            DeltaValue::Delta { baseline_tick, delta } => {
+                let baseline = match cache.snapshot_at_tick(*baseline_tick) {
+                    Some(s) if s.tick() == *baseline_tick => s.component(),
+                    _ => {
+                        debug!("baseline tick: {baseline_tick} is not cached");
+                        continue;
+                    }
+                };
+                C::apply_delta(baseline, delta, config.step)
+            }
+        };
+
+        match cache.insert(value, delta.tick, now) {
+            Ok(()) => {
+                *c = value;
+                acks.send(AcknowledgeDelta::new(delta.entity, delta.tick));
+            }
+            Err(e) => warn!("discarding delta snapshot: {e}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::control::NetworkTranslation3D;
+    use super::*;
+
+    const STEP: f32 = 0.01;
+
+    fn config() -> DeltaConfig<NetworkTranslation3D> {
+        DeltaConfig::new(STEP, 8, 64, 4)
+    }
+
+    #[test]
+    fn delta_steps_round_trip() {
+        for (value, baseline) in [
+            (0.0, 0.0), 
+            (1.234, 1.0), 
+            (-5.5, 3.25), 
+            (100.004, 99.996)
+        ] {
+            let delta = delta_steps(value, baseline, STEP)
+            .expect("offset should fit");
+            let applied = apply_delta_steps(baseline, delta, STEP);
+            assert!((applied - value).abs() <= STEP * 0.5 + 1e-4, "value: {value}");
+        }
+    }
+
+    #[test]
+    fn delta_steps_out_of_range() {
+        let far = (i16::MAX as f32 + 2.0) * STEP;
+        assert!(delta_steps(far, 0.0, STEP).is_none());
+        assert!(delta_steps(-far, 0.0, STEP).is_none());
+        assert!(delta_steps(1.0, 0.0, 0.0).is_none());
+    }
+
+    #[test]
+    fn baseline_is_snapshot_at_acknowledged_tick() {
+        let mut cache = ComponentCache::with_init(
+            NetworkTranslation3D(Vec3::X), 10, 0.0, 8
+        ).expect("could not insert initial snapshot");
+        cache.insert(NetworkTranslation3D(Vec3::Y), 12, 0.1)
+        .expect("could not insert snapshot");
+
+        assert!(cache.snapshot_at_tick(9).is_none());
+
+        let s = cache.snapshot_at_tick(11).unwrap();
+        assert_eq!(s.tick(), 10);
+        assert_eq!(s.component().0, Vec3::X);
+
+        let s = cache.snapshot_at_tick(12).unwrap();
+        assert_eq!(s.tick(), 12);
+        assert_eq!(s.component().0, Vec3::Y);
+
+        let value = NetworkTranslation3D(Vec3::new(0.5, 1.0, 0.0));
+        let delta = value.delta(cache.snapshot_at_tick(12).unwrap().component(), STEP)
+        .expect("offset should fit");
+        let applied = NetworkTranslation3D::apply_delta(&NetworkTranslation3D(Vec3::Y), &delta, STEP);
+        assert!((applied.0 - value.0).abs().max_element() <= STEP * 0.5 + 1e-4);
+    }
+
+    fn receive_app() -> (App, Entity) {
+        let mut app = App::new();
+        app.insert_resource(NetworkClock::default())
+        .insert_resource(config())
+        .add_event::<DeltaReplication<NetworkTranslation3D>>()
+        .add_event::<AcknowledgeDelta<NetworkTranslation3D>>()
+        .add_systems(Update, client_receive_delta_system::<NetworkTranslation3D>);
+
+        let e = app.world_mut().spawn((
+            NetworkTranslation3D(Vec3::ZERO),
+            ComponentCache::with_init(NetworkTranslation3D(Vec3::ZERO), 10, 0.0, 8)
+            .expect("could not insert initial snapshot")
+        )).id();
+        (app, e)
+    }
+
+    fn drain_acks(app: &mut App) -> Vec<u32> {
+        app.world_mut()
+        .resource_mut::<Events<AcknowledgeDelta<NetworkTranslation3D>>>()
+        .drain()
+        .map(|a| a.tick)
+        .collect()
+    }
+
+    #[test]
+    fn client_discards_stale_tick() {
+        let (mut app, e) = receive_app();
+
+        for tick in [10, 8] {
+            app.world_mut().send_event(DeltaReplication{
+                entity: e,
+                tick,
+                value: DeltaValue::Absolute(NetworkTranslation3D(Vec3::ONE))
+            });
+        }
+        app.update();
+        assert_eq!(app.world().get::<NetworkTranslation3D>(e).unwrap().0, Vec3::ZERO);
+        assert!(drain_acks(&mut app).is_empty());
+
+        app.world_mut().send_event(DeltaReplication{
+            entity: e,
+            tick: 11,
+            value: DeltaValue::<NetworkTranslation3D>::Delta{
+                baseline_tick: 10,
+                delta: [100, 0, 0]
+            }
+        });
+        app.update();
+        let x = app.world().get::<NetworkTranslation3D>(e).unwrap().0.x;
+        assert!((x - 100.0 * STEP).abs() < 1e-4, "x: {x}");
+        assert_eq!(drain_acks(&mut app), vec![11]);
+    }
+}
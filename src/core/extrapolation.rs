@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Clone)]
+pub struct ExtrapolationConfig {
+    /// remote entities are projected forward at most this seconds
+    pub max_time: f32,
+    /// extrapolated offset is blended back with this half life
+    pub blend_half_life: f32
+}
+
+impl ExtrapolationConfig {
+    #[inline]
+    pub fn blend_ratio(&self, delta: f32) -> f32 {
+        if self.blend_half_life <= 0.0 {
+            return 0.0;
+        }
+
+        0.5f32.powf(delta / self.blend_half_life)
+    }
+}
+
+#[derive(Component, Default)]
+pub struct TranslationExtrapolation {
+    offset: Vec3,
+    rendered: Vec3,
+    is_extrapolating: bool
+}
+
+impl TranslationExtrapolation {
+    #[inline]
+    pub fn offset(&self) -> Vec3 {
+        self.offset
+    }
+
+    #[inline]
+    pub fn is_extrapolating(&self) -> bool {
+        self.is_extrapolating
+    }
+
+    #[inline]
+    pub(crate) fn extrapolate(&mut self, velocity: Vec3, time: f32) {
+        self.offset = velocity * time;
+        self.is_extrapolating = true;
+    }
+
+    /// keeps rendered translation continuous when snapshots resume 
+    #[inline]
+    pub(crate) fn blend(&mut self, base: Vec3, ratio: f32) {
+        if self.is_extrapolating {
+            self.offset = self.rendered - base;
+            self.is_extrapolating = false;
+        }
+        self.offset *= ratio;
+    }
+
+    #[inline]
+    pub(crate) fn render(&mut self, base: Vec3) -> Vec3 {
+        self.rendered = base + self.offset;
+        self.rendered
+    }
+}
+
+#[derive(Component)]
+pub struct RotationExtrapolation {
+    offset: Quat,
+    rendered: Quat,
+    is_extrapolating: bool
+}
+
+impl Default for RotationExtrapolation {
+    #[inline]
+    fn default() -> Self {
+        Self { 
+            offset: Quat::IDENTITY, 
+            rendered: Quat::IDENTITY,
+            is_extrapolating: false 
+        }
+    }
+}
+
+impl RotationExtrapolation {
+    #[inline]
+    pub fn offset(&self) -> Quat {
+        self.offset
+    }
+
+    #[inline]
+    pub fn is_extrapolating(&self) -> bool {
+        self.is_extrapolating
+    }
+
+    #[inline]
+    pub(crate) fn extrapolate(&mut self, angular_velocity: Vec3, time: f32) {
+        self.offset = Quat::from_scaled_axis(angular_velocity * time);
+        self.is_extrapolating = true;
+    }
+
+    #[inline]
+    pub(crate) fn blend(&mut self, base: Quat, ratio: f32) {
+        if self.is_extrapolating {
+            self.offset = self.rendered * base.inverse();
+            self.is_extrapolating = false;
+        }
+        self.offset = Quat::IDENTITY.slerp(self.offset, ratio);
+    }
+
+    #[inline]
+    pub(crate) fn render(&mut self, base: Quat) -> Quat {
+        self.rendered = (self.offset * base).normalize();
+        self.rendered
+    }
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use bevy_replicon::core::replicon_tick::RepliconTick;
+use super::RenderDelay;
+
+/// opt-in, keeps entity alive after replicated despawn 
+/// until interpolation reaches latest snapshot
+#[derive(Component, Default)]
+pub struct GhostDespawn {
+    /// additional seconds for fade out
+    pub fade_time: f32
+}
+
+#[derive(Component)]
+pub struct Ghost {
+    remaining: f32
+}
+
+impl Ghost {
+    #[inline]
+    pub fn remaining(&self) -> f32 {
+        self.remaining
+    }
+}
+
+#[derive(Event)]
+pub struct GhostSpawned {
+    pub entity: Entity,
+    /// seconds until ghost is despawned
+    pub remaining: f32
+}
+
+pub(crate) fn ghost_despawn_fn(mut entity: EntityWorldMut, _: RepliconTick) {
+    let Some(fade_time) = entity.get::<GhostDespawn>()
+    .map(|g| g.fade_time) else {
+        entity.despawn_recursive();
+        return;
+    };
+
+    let delay = entity.world_scope(|world| {
+        world.get_resource::<RenderDelay>()
+        .map(|d| d.delay() as f32)
+        .unwrap_or(0.0)
+    });
+    let remaining = delay + fade_time;
+    let id = entity.id();
+
+    entity.insert(Ghost{ remaining });
+    entity.world_scope(|world| {
+        world.send_event(GhostSpawned{ entity: id, remaining });
+    });
+    debug!("ghost: {id:?} remains for: {remaining}");
+}
+
+pub(crate) fn ghost_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Ghost)>,
+    time: Res<Time>
+) {
+    for (e, mut ghost) in query.iter_mut() {
+        ghost.remaining -= time.delta_seconds();
+        if ghost.remaining <= 0.0 {
+            commands.entity(e)
+            .despawn_recursive();
+            debug!("ghost: {e:?} despawned");
+        }
+    }
+}
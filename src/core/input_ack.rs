@@ -0,0 +1,81 @@
+use std::marker::PhantomData;
+use serde::{Serialize, Deserialize};
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use super::NetworkMovement;
+
+#[derive(Component)]
+pub struct InputAck<E: NetworkMovement> {
+    index: Option<usize>,
+    tick: u32,
+    phantom: PhantomData<E>
+}
+
+impl<E: NetworkMovement> Default for InputAck<E> {
+    #[inline]
+    fn default() -> Self {
+        Self { 
+            index: None, 
+            tick: 0, 
+            phantom: PhantomData::<E> 
+        }
+    }
+}
+
+impl<E: NetworkMovement> InputAck<E> {
+    /// last movement index that is processed on server
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// server tick when the index is acknowledged
+    #[inline]
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    #[inline]
+    pub fn is_acknowledged(&self, index: usize) -> bool {
+        match self.index {
+            Some(idx) => index <= idx,
+            None => false
+        }
+    }
+
+    #[inline]
+    pub fn try_set(&mut self, index: usize, tick: u32) -> bool {
+        if self.is_acknowledged(index) {
+            return false;
+        }
+
+        self.index = Some(index);
+        self.tick = tick;
+        true
+    }
+}
+
+#[derive(Event, Serialize, Deserialize)]
+pub struct AcknowledgeInput<E: NetworkMovement> {
+    pub index: u64,
+    pub tick: u32,
+    phantom: PhantomData<E>
+}
+
+impl<E: NetworkMovement> AcknowledgeInput<E> {
+    #[inline]
+    pub fn new(index: usize, tick: u32) -> Self {
+        Self { 
+            index: index as u64, 
+            tick, 
+            phantom: PhantomData::<E> 
+        }
+    }
+
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+}
+
+pub type SendInputAck<E> = ToClients<AcknowledgeInput<E>>;
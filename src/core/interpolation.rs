@@ -1,6 +1,145 @@
+use std::marker::PhantomData;
 use bevy::prelude::*;
+use super::LatestConfirmedTick;
 
 #[derive(Resource, Clone)]
 pub struct InterpolationConfig {
-    pub network_tick_delta: f64
+    pub network_tick_delta: f64,
+    /// remote entities are rendered at least this seconds behind
+    pub min_render_delay: f64,
+    pub max_render_delay: f64,
+    /// render delay is network tick delta + measured jitter * this
+    pub jitter_multiplier: f64,
+    /// weight of new jitter sample
+    pub jitter_smoothing: f64
+}
+
+#[derive(Resource, Default)]
+pub struct RenderDelay {
+    delay: f64,
+    jitter: f64,
+    last_arrival: Option<(f64, u32)>
+}
+
+impl RenderDelay {
+    #[inline]
+    pub fn delay(&self) -> f64 {
+        self.delay
+    }
+
+    #[inline]
+    pub fn jitter(&self) -> f64 {
+        self.jitter
+    }
+
+    pub fn update(&mut self, arrival: f64, tick: u32, config: &InterpolationConfig) {
+        if let Some((last_arrival, last_tick)) = self.last_arrival {
+            let expected = tick.saturating_sub(last_tick) as f64 
+            * config.network_tick_delta;
+            let sample = ((arrival - last_arrival) - expected).abs();
+            self.jitter += (sample - self.jitter) * config.jitter_smoothing;
+        }
+
+        self.last_arrival = Some((arrival, tick));
+        self.delay = (config.network_tick_delta + self.jitter * config.jitter_multiplier)
+        .clamp(config.min_render_delay, config.max_render_delay);
+    }
+}
+
+#[derive(Resource)]
+pub struct InterpolateCacheSize<C: Component> {
+    pub cache_size: usize,
+    phantom: PhantomData<C>
+}
+
+impl<C: Component> InterpolateCacheSize<C> {
+    #[inline]
+    pub fn new(cache_size: usize) -> Self {
+        Self { 
+            cache_size, 
+            phantom: PhantomData::<C> 
+        }
+    }
+}
+
+/// marks remote entity to interpolate translation with cubic hermite
+/// using replicated linear velocity at both snapshots
+#[derive(Component, Default)]
+pub struct HermiteInterpolation;
+
+#[inline]
+pub fn hermite(p0: Vec3, v0: Vec3, p1: Vec3, v1: Vec3, span: f32, per: f32) -> Vec3 {
+    let t2 = per * per;
+    let t3 = t2 * per;
+    let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+    let h10 = t3 - 2.0 * t2 + per;
+    let h01 = -2.0 * t3 + 3.0 * t2;
+    let h11 = t3 - t2;
+    
+    p0 * h00 + v0 * (h10 * span) + p1 * h01 + v1 * (h11 * span)
+}
+
+/// maps server ticks onto local monotonic time
+#[derive(Resource, Default)]
+pub struct NetworkTimeline {
+    offset: Option<f64>
+}
+
+impl NetworkTimeline {
+    #[inline]
+    pub fn is_synced(&self) -> bool {
+        self.offset.is_some()
+    }
+
+    /// local time when server tick happened, shifted by latency
+    #[inline]
+    pub fn time_of(&self, tick: f64, config: &InterpolationConfig) -> Option<f64> {
+        self.offset
+        .map(|o| tick * config.network_tick_delta + o)
+    }
+
+    /// fractional server tick that maps onto local time
+    #[inline]
+    pub fn tick_at(&self, time: f64, config: &InterpolationConfig) -> Option<f64> {
+        if config.network_tick_delta <= 0.0 {
+            return None;
+        }
+
+        self.offset
+        .map(|o| (time - o) / config.network_tick_delta)
+    }
+
+    pub fn update(&mut self, arrival: f64, tick: u32, config: &InterpolationConfig) {
+        let sample = arrival - tick as f64 * config.network_tick_delta;
+        self.offset = match self.offset {
+            // timeline jumped, for example after reconnection
+            Some(o) if (sample - o).abs() <= config.max_render_delay => {
+                Some(o + (sample - o) * config.jitter_smoothing)
+            }
+            _ => Some(sample)
+        };
+    }
+}
+
+pub(crate) fn network_timeline_system(
+    latest_confirmed: Res<LatestConfirmedTick>,
+    mut timeline: ResMut<NetworkTimeline>,
+    mut render_delay: ResMut<RenderDelay>,
+    config: Res<InterpolationConfig>,
+    time: Res<Time<Real>>
+) {
+    if !latest_confirmed.is_changed() {
+        return;
+    }
+
+    let arrival = time.elapsed_seconds_f64();
+    let tick = latest_confirmed.get()
+    .get();
+    timeline.update(arrival, tick, &config);
+    render_delay.update(arrival, tick, &config);
+    trace!(
+        "render delay: {} jitter: {}", 
+        render_delay.delay(), 
+        render_delay.jitter()
+    );
 }
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use bevy_replicon::server::server_tick::ServerTick;
+
+/// forces replication of transform under threshold, 
+/// periodically and once more when entity comes to rest,
+/// so clients converge to exact server state
+#[derive(Component, Clone, Copy)]
+pub struct KeyframeReplication {
+    /// server ticks between forced writes, 0 only forces write at rest
+    pub interval: u32,
+    /// server ticks without change before entity is at rest
+    pub rest_ticks: u32,
+    last: Option<Transform>,
+    observed_tick: u32,
+    unchanged_ticks: u32
+}
+
+impl KeyframeReplication {
+    #[inline]
+    pub fn new(interval: u32, rest_ticks: u32) -> Self {
+        Self { 
+            interval, 
+            rest_ticks,
+            last: None,
+            observed_tick: 0,
+            unchanged_ticks: 0 
+        }
+    }
+
+    #[inline]
+    pub fn is_due(&self, last_tick: u32, tick: u32) -> bool {
+        self.interval > 0 
+        && tick.saturating_sub(last_tick) >= self.interval
+    }
+
+    /// transform did not change for rest ticks
+    #[inline]
+    pub fn is_at_rest(&self) -> bool {
+        self.last.is_some() 
+        && self.unchanged_ticks >= self.rest_ticks.max(1)
+    }
+
+    /// entity is at rest or keyframe interval elapsed
+    #[inline]
+    pub fn is_forced(&self, last_tick: u32, tick: u32) -> bool {
+        self.is_at_rest() || self.is_due(last_tick, tick)
+    }
+
+    /// counted per server tick, not per frame,
+    /// so frames without a tick are not taken as rest
+    #[inline]
+    pub(crate) fn observe(&mut self, transform: &Transform, tick: u32) {
+        if self.last.is_some() && tick == self.observed_tick {
+            return;
+        }
+
+        let elapsed = tick.saturating_sub(self.observed_tick);
+        self.observed_tick = tick;
+        if self.last.as_ref() == Some(transform) {
+            self.unchanged_ticks = self.unchanged_ticks.saturating_add(elapsed);
+        } else {
+            self.last = Some(*transform);
+            self.unchanged_ticks = 0;
+        }
+    }
+}
+
+pub(crate) fn keyframe_rest_system(
+    mut query: Query<(&Transform, &mut KeyframeReplication)>,
+    server_tick: Res<ServerTick>
+) {
+    let tick = server_tick.get();
+    for (transform, mut keyframe) in query.iter_mut() {
+        keyframe.observe(transform, tick);
+    }
+}
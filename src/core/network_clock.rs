@@ -0,0 +1,84 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering}
+};
+use bevy::{
+    prelude::*,
+    utils::Instant
+};
+
+pub trait Clock: Send + Sync + 'static {
+    /// seconds since clock origin
+    fn now(&self) -> f64;
+}
+
+pub struct MonotonicClock {
+    origin: Instant
+}
+
+impl Default for MonotonicClock {
+    #[inline]
+    fn default() -> Self {
+        Self { origin: Instant::now() }
+    }
+}
+
+impl Clock for MonotonicClock {
+    #[inline]
+    fn now(&self) -> f64 {
+        self.origin.elapsed()
+        .as_secs_f64()
+    }
+}
+
+/// clock that only moves when told, for tests and replays
+#[derive(Clone, Default)]
+pub struct ManualClock {
+    bits: Arc<AtomicU64>
+}
+
+impl ManualClock {
+    #[inline]
+    pub fn set(&self, now: f64) {
+        self.bits.store(now.to_bits(), Ordering::Release);
+    }
+
+    #[inline]
+    pub fn advance(&self, delta: f64) {
+        self.set(self.now() + delta);
+    }
+}
+
+impl Clock for ManualClock {
+    #[inline]
+    fn now(&self) -> f64 {
+        f64::from_bits(self.bits.load(Ordering::Acquire))
+    }
+}
+
+#[derive(Resource, Clone)]
+pub struct NetworkClock(Arc<dyn Clock>);
+
+impl Default for NetworkClock {
+    #[inline]
+    fn default() -> Self {
+        Self::monotonic()
+    }
+}
+
+impl NetworkClock {
+    #[inline]
+    pub fn new(clock: impl Clock) -> Self {
+        Self(Arc::new(clock))
+    }
+
+    #[inline]
+    pub fn monotonic() -> Self {
+        Self::new(MonotonicClock::default())
+    }
+
+    #[inline]
+    pub fn now(&self) -> f64 {
+        self.0.now()
+    }
+}
@@ -1,4 +1,5 @@
-use serde::{Serialize, de::DeserializeOwned};
+use std::collections::VecDeque;
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
 use bevy::prelude::*;
 
 pub trait NetworkEvent
@@ -6,4 +7,64 @@ pub trait NetworkEvent
     fn index(&self) -> usize;
     fn tick(&self) -> u32;
     fn validate(&self) -> anyhow::Result<()>;
+
+    /// restores index sent in wrapped form,
+    /// against the latest index receiver has
+    #[inline]
+    fn unwrap_index(&mut self, _latest_index: usize) {}
+}
+
+/// carries latest events together with older unacknowledged ones,
+/// so single lost packet does not lose an event
+#[derive(Event, Serialize, Deserialize, Clone)]
+pub struct RedundantEvents<E: NetworkEvent> {
+    pub events: Vec<E>
+}
+
+#[derive(Resource)]
+pub struct RedundantEventBuffer<E: NetworkEvent> {
+    buffer: VecDeque<E>,
+    redundancy: usize
+}
+
+impl<E: NetworkEvent> RedundantEventBuffer<E> {
+    #[inline]
+    pub fn new(redundancy: usize) -> Self {
+        Self { 
+            buffer: VecDeque::with_capacity(redundancy), 
+            redundancy 
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    #[inline]
+    pub fn push(&mut self, event: E) {
+        if self.buffer.len() >= self.redundancy {
+            self.buffer.pop_front();
+        }
+        self.buffer.push_back(event);
+    }
+
+    #[inline]
+    pub fn discard_acknowledged(&mut self, last_index: usize) {
+        self.buffer.retain(|e| e.index() > last_index);
+    }
+
+    #[inline]
+    pub fn to_redundant_events(&self) -> RedundantEvents<E> {
+        RedundantEvents { 
+            events: self.buffer.iter()
+            .cloned()
+            .collect() 
+        }
+    }
 }
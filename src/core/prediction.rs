@@ -1,4 +1,7 @@
-use std::marker::PhantomData;
+use std::{
+    any::TypeId, 
+    marker::PhantomData
+};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use bevy::prelude::*;
 use bevy_replicon::prelude::*;
@@ -45,20 +48,32 @@ impl<C: Component + Serialize + DeserializeOwned> PredioctionError<C> {
 #[derive(Event, Serialize, Deserialize)]
 pub struct ForceReplicateTranslation<T>
 where T: NetworkTranslation {
-    pub last_index: u64,
-    phantom: PhantomData<T>
+    pub translation: T,
+    pub tick: u32,
+    pub last_index: u64
 }
 
 impl<T> ForceReplicateTranslation<T>
 where T: NetworkTranslation {
     #[inline]
-    pub fn new(index: usize) -> Self {
+    pub fn new(translation: T, tick: u32, index: usize) -> Self {
         Self { 
-            last_index: index as u64,
-            phantom: PhantomData::<T> 
+            translation,
+            tick,
+            last_index: index as u64
         }
     }
 
+    #[inline]
+    pub fn translation(&self) -> &T {
+        &self.translation
+    }
+
+    #[inline]
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
     #[inline]
     pub fn last_index(&self) -> usize {
         self.last_index as usize
@@ -70,20 +85,32 @@ pub type CorrectTranslation<T> = ToClients<ForceReplicateTranslation<T>>;
 #[derive(Event, Serialize, Deserialize)]
 pub struct ForceReplicateRotation<R>
 where R: NetworkRotation {
-    pub last_index: u64,
-    phantom: PhantomData<R>
+    pub rotation: R,
+    pub tick: u32,
+    pub last_index: u64
 }
 
 impl<R> ForceReplicateRotation<R>
 where R: NetworkRotation {
     #[inline]
-    pub fn new(index: usize) -> Self {
+    pub fn new(rotation: R, tick: u32, index: usize) -> Self {
         Self { 
-            last_index: index as u64, 
-            phantom: PhantomData::<R> 
+            rotation,
+            tick,
+            last_index: index as u64
         }
     }
 
+    #[inline]
+    pub fn rotation(&self) -> &R {
+        &self.rotation
+    }
+
+    #[inline]
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
     #[inline]
     pub fn last_index(&self) -> usize {
         self.last_index as usize
@@ -91,3 +118,137 @@ where R: NetworkRotation {
 }
 
 pub type CorrectRotation<R> = ToClients<ForceReplicateRotation<R>>;
+
+#[derive(Event, Serialize, Deserialize)]
+pub struct ForceReplicatePredicted<C>
+where C: Component + Serialize + DeserializeOwned {
+    pub component: C,
+    pub tick: u32,
+    pub last_index: u64
+}
+
+impl<C> ForceReplicatePredicted<C>
+where C: Component + Serialize + DeserializeOwned {
+    #[inline]
+    pub fn new(component: C, tick: u32, index: usize) -> Self {
+        Self { 
+            component, 
+            tick, 
+            last_index: index as u64 
+        }
+    }
+
+    #[inline]
+    pub fn component(&self) -> &C {
+        &self.component
+    }
+
+    #[inline]
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    #[inline]
+    pub fn last_index(&self) -> usize {
+        self.last_index as usize
+    }
+}
+
+pub type CorrectPredicted<C> = ToClients<ForceReplicatePredicted<C>>;
+
+#[derive(Component, Default)]
+pub struct Reconciliation {
+    last_index: Option<usize>,
+    translation: Option<Vec3>,
+    rotation: Option<Quat>,
+    corrected: Vec<TypeId>,
+    replay: usize
+}
+
+impl Reconciliation {
+    #[inline]
+    pub fn last_index(&self) -> Option<usize> {
+        self.last_index
+    }
+
+    #[inline]
+    pub fn is_requested(&self) -> bool {
+        self.last_index.is_some()
+    }
+
+    #[inline]
+    pub fn request(&mut self, last_index: usize) -> bool {
+        match self.last_index {
+            // older correction than requested one is already stale
+            Some(idx) if idx > last_index => return false,
+            Some(idx) if idx == last_index => return true,
+            _ => ()
+        }
+
+        self.last_index = Some(last_index);
+        self.translation = None;
+        self.rotation = None;
+        self.corrected.clear();
+        true
+    }
+
+    #[inline]
+    pub fn request_translation(&mut self, last_index: usize, translation: Vec3) {
+        if self.request(last_index) {
+            self.translation = Some(translation);
+        }
+    }
+
+    #[inline]
+    pub fn request_rotation(&mut self, last_index: usize, rotation: Quat) {
+        if self.request(last_index) {
+            self.rotation = Some(rotation);
+        }
+    }
+
+    #[inline]
+    pub fn request_component<C: Component>(&mut self, last_index: usize) {
+        if self.request(last_index) {
+            self.corrected.push(TypeId::of::<C>());
+        }
+    }
+
+    #[inline]
+    pub fn is_corrected<C: Component>(&self) -> bool {
+        self.corrected.contains(&TypeId::of::<C>())
+    }
+
+    #[inline]
+    pub fn translation(&self) -> Option<Vec3> {
+        self.translation
+    }
+
+    #[inline]
+    pub fn rotation(&self) -> Option<Quat> {
+        self.rotation
+    }
+
+    /// count of rewound movements in frontier that were simulated before
+    #[inline]
+    pub fn replay(&self) -> usize {
+        self.replay
+    }
+
+    #[inline]
+    pub(crate) fn set_replay(&mut self, replay: usize) {
+        self.replay = replay;
+    }
+
+    #[inline]
+    pub(crate) fn take_replay(&mut self) -> usize {
+        std::mem::take(&mut self.replay)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.last_index = None;
+        self.translation = None;
+        self.rotation = None;
+        self.corrected.clear();
+    }
+}
@@ -0,0 +1,147 @@
+use serde::{Serialize, Deserialize};
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    server::server_tick::ServerTick
+};
+use super::{InterpolationConfig, LatestConfirmedTick};
+
+#[derive(Resource, Clone)]
+pub struct ServerTickEstimateConfig {
+    /// seconds between pings
+    pub ping_interval: f64,
+    /// weight of new rtt sample
+    pub rtt_smoothing: f64,
+    /// estimate is moved toward target by this ratio per pong
+    pub correction: f64,
+    /// estimate snaps to target when error is over this ticks
+    pub snap_threshold: f64
+}
+
+#[derive(Event, Serialize, Deserialize, Clone)]
+pub struct TickPing {
+    pub client_time: f64
+}
+
+#[derive(Event, Serialize, Deserialize, Clone)]
+pub struct TickPong {
+    pub client_time: f64,
+    pub server_tick: u32
+}
+
+pub type SendTickPong = ToClients<TickPong>;
+
+#[derive(Resource, Default)]
+pub struct ServerTickEstimate {
+    tick: f64,
+    rtt: Option<f64>,
+    last_ping: Option<f64>
+}
+
+impl ServerTickEstimate {
+    /// estimated current tick on server
+    #[inline]
+    pub fn tick(&self) -> u32 {
+        self.tick.max(0.0) as u32
+    }
+
+    /// progress toward next server tick in 0.0..1.0
+    #[inline]
+    pub fn fraction(&self) -> f32 {
+        self.tick.fract() as f32
+    }
+
+    #[inline]
+    pub fn rtt(&self) -> Option<f64> {
+        self.rtt
+    }
+
+    #[inline]
+    pub fn is_synced(&self) -> bool {
+        self.rtt.is_some()
+    }
+}
+
+pub(crate) fn client_tick_ping_system(
+    mut estimate: ResMut<ServerTickEstimate>,
+    mut pings: EventWriter<TickPing>,
+    config: Res<ServerTickEstimateConfig>,
+    time: Res<Time<Real>>
+) {
+    let now = time.elapsed_seconds_f64();
+    if let Some(last) = estimate.last_ping {
+        if now - last < config.ping_interval {
+            return;
+        }
+    }
+
+    estimate.last_ping = Some(now);
+    pings.send(TickPing { client_time: now });
+}
+
+pub(crate) fn server_tick_pong_system(
+    mut pings: EventReader<FromClient<TickPing>>,
+    mut pongs: EventWriter<SendTickPong>,
+    server_tick: Res<ServerTick>
+) {
+    for FromClient { client_id, event } in pings.read() {
+        pongs.send(SendTickPong{
+            mode: SendMode::Direct(*client_id),
+            event: TickPong { 
+                client_time: event.client_time, 
+                server_tick: server_tick.get() 
+            }
+        });
+    }
+}
+
+pub(crate) fn client_tick_estimate_system(
+    mut estimate: ResMut<ServerTickEstimate>,
+    mut pongs: EventReader<TickPong>,
+    latest_confirmed: Res<LatestConfirmedTick>,
+    config: Res<ServerTickEstimateConfig>,
+    interpolation_config: Res<InterpolationConfig>,
+    time: Res<Time<Real>>
+) {
+    let tick_delta = interpolation_config.network_tick_delta;
+    if tick_delta <= 0.0 {
+        return;
+    }
+
+    let previous = estimate.tick;
+    estimate.tick += time.delta_seconds_f64() / tick_delta;
+    let mut snapped = false;
+
+    let now = time.elapsed_seconds_f64();
+    for pong in pongs.read() {
+        let sample = (now - pong.client_time).max(0.0);
+        let rtt = match estimate.rtt {
+            Some(rtt) => rtt + (sample - rtt) * config.rtt_smoothing,
+            None => sample
+        };
+        estimate.rtt = Some(rtt);
+
+        // pong was sent half rtt ago
+        let target = pong.server_tick as f64 + rtt * 0.5 / tick_delta;
+        let error = target - estimate.tick;
+        if error.abs() > config.snap_threshold {
+            estimate.tick = target;
+            snapped = true;
+        } else {
+            estimate.tick += error * config.correction;
+        }
+        trace!("server tick estimate: {} rtt: {}", estimate.tick, rtt);
+    }
+
+    // stamped ticks should not go backward by small corrections
+    if !snapped && estimate.tick < previous {
+        estimate.tick = previous;
+    }
+
+    // server has at least reached confirmed tick
+    let confirmed = latest_confirmed.get()
+    .get() as f64;
+    if estimate.tick < confirmed {
+        estimate.tick = confirmed;
+    }
+}
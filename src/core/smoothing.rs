@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Clone)]
+pub struct SmoothingConfig {
+    pub translation_half_life: f32,
+    pub rotation_half_life: f32,
+    /// error over this distance is not smoothed, just teleports
+    pub max_translation_error: f32,
+    /// error over this degrees is not smoothed, just teleports
+    pub max_rotation_error: f32
+}
+
+#[derive(Component)]
+pub struct PredictionSmoothing {
+    translation_offset: Vec3,
+    rotation_offset: Quat,
+    pending: Option<(Vec3, Quat)>
+}
+
+impl Default for PredictionSmoothing {
+    #[inline]
+    fn default() -> Self {
+        Self { 
+            translation_offset: Vec3::ZERO, 
+            rotation_offset: Quat::IDENTITY, 
+            pending: None 
+        }
+    }
+}
+
+/// child of smoothed entity that is rendered with offset,
+/// base is its local transform without offset
+#[derive(Component, Default)]
+pub struct SmoothingVisual {
+    pub base: Transform
+}
+
+impl SmoothingVisual {
+    #[inline]
+    pub fn new(base: Transform) -> Self {
+        Self { base }
+    }
+}
+
+impl PredictionSmoothing {
+    #[inline]
+    pub fn translation_offset(&self) -> Vec3 {
+        self.translation_offset
+    }
+
+    #[inline]
+    pub fn rotation_offset(&self) -> Quat {
+        self.rotation_offset
+    }
+
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    #[inline]
+    pub fn visual_translation(&self, translation: Vec3) -> Vec3 {
+        translation + self.translation_offset
+    }
+
+    #[inline]
+    pub fn visual_rotation(&self, rotation: Quat) -> Quat {
+        self.rotation_offset * rotation
+    }
+
+    /// offset in local space of root transform
+    #[inline]
+    pub fn local_offset(&self, root: &Transform) -> Transform {
+        let inverse = root.rotation.inverse();
+        Transform{
+            translation: inverse * self.translation_offset / root.scale,
+            rotation: (inverse * self.rotation_offset * root.rotation).normalize(),
+            scale: Vec3::ONE
+        }
+    }
+
+    #[inline]
+    pub(crate) fn begin(&mut self, transform: &Transform) {
+        // keep the first one, it is what is rendered now
+        if self.pending.is_some() {
+            return;
+        }
+
+        self.pending = Some((
+            self.visual_translation(transform.translation),
+            self.visual_rotation(transform.rotation)
+        ));
+    }
+
+    #[inline]
+    pub(crate) fn resolve(&mut self, transform: &Transform, config: &SmoothingConfig) {
+        let Some((translation, rotation)) = self.pending.take() else {
+            return;
+        };
+
+        let translation_offset = translation - transform.translation;
+        self.translation_offset = if translation_offset.length() > config.max_translation_error {
+            Vec3::ZERO
+        } else {
+            translation_offset
+        };
+        
+        let rotation_offset = (rotation * transform.rotation.inverse()).normalize();
+        self.rotation_offset = if rotation_offset.angle_between(Quat::IDENTITY).to_degrees() 
+        > config.max_rotation_error {
+            Quat::IDENTITY
+        } else {
+            rotation_offset
+        };
+    }
+
+    #[inline]
+    pub(crate) fn decay(&mut self, delta: f32, config: &SmoothingConfig) {
+        self.translation_offset = if config.translation_half_life > 0.0 {
+            self.translation_offset * 0.5f32.powf(delta / config.translation_half_life)
+        } else {
+            Vec3::ZERO
+        };
+
+        self.rotation_offset = if config.rotation_half_life > 0.0 {
+            Quat::IDENTITY.slerp(
+                self.rotation_offset, 
+                0.5f32.powf(delta / config.rotation_half_life)
+            )
+        } else {
+            Quat::IDENTITY
+        };
+    }
+}
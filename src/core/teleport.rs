@@ -0,0 +1,46 @@
+use serde::{Serialize, Deserialize};
+use bevy::prelude::*;
+use super::ReplicationConfig;
+
+/// bumped by server when entity jumps discontinuously,
+/// clients snap instead of interpolating across the jump
+#[derive(Component, Serialize, Deserialize, Default)]
+pub struct NetworkTeleport {
+    count: u32,
+    #[serde(skip)]
+    last_translation: Option<Vec3>
+}
+
+impl NetworkTeleport {
+    #[inline]
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// call when entity is moved on purpose, like respawn
+    #[inline]
+    pub fn bump(&mut self) {
+        self.count = self.count.wrapping_add(1);
+    }
+}
+
+pub(crate) fn detect_teleport_system(
+    mut query: Query<(&Transform, &mut NetworkTeleport), Changed<Transform>>,
+    config: Res<ReplicationConfig>
+) {
+    for (transform, mut teleport) in query.iter_mut() {
+        let translation = transform.translation;
+        let last = teleport.bypass_change_detection()
+        .last_translation
+        .replace(translation);
+
+        let Some(last) = last else {
+            continue;
+        };
+
+        if last.distance_squared(translation) > config.teleport_threshold_sq() {
+            teleport.bump();
+            debug!("teleport detected: {} -> {}", last, translation);
+        }
+    }
+}
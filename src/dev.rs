@@ -5,7 +5,10 @@ pub mod game_server;
 
 use anyhow::bail;
 use rand::prelude::*;
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    ecs::query::QueryItem
+};
 use bevy_replicon::prelude::*;
 //use bevy_replicon_renet::renet::transport::NetcodeTransportError;
 use bevy_rapier3d::prelude::*;
@@ -17,11 +20,7 @@ pub struct GameCommonPlugin;
 
 impl Plugin for GameCommonPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(PlayerMovementParams{
-            base_speed: BASE_SPEED,
-            base_angular_speed: BASE_ANGULAR_SPEED,
-        })
-        .add_plugins((
+        app.add_plugins((
             NetworkBootPlugin {
                 transform_axis: TransformAxis{
                     translation: default(),
@@ -29,10 +28,16 @@ impl Plugin for GameCommonPlugin {
                 },
                 replication_config: ReplicationConfig{
                     translation_threshold: TRANSLATION_REPLICATION_THRESHOLD,
-                    rotation_threashold: ROTATION_REPLICATION_THRESHOLD
+                    rotation_threashold: ROTATION_REPLICATION_THRESHOLD,
+                    scale_threshold: SCALE_REPLICATION_THRESHOLD,
+                    teleport_threshold: TELEPORT_THRESHOLD
                 },
                 interpolation_config: InterpolationConfig { 
-                    network_tick_delta: DEV_NETWORK_TICK_DELTA64 
+                    network_tick_delta: DEV_NETWORK_TICK_DELTA64,
+                    min_render_delay: MIN_RENDER_DELAY,
+                    max_render_delay: MAX_RENDER_DELAY,
+                    jitter_multiplier: JITTER_MULTIPLIER,
+                    jitter_smoothing: JITTER_SMOOTHING
                 },
                 prediction_config: PredictionConfig { 
                     translation_threshold: TRANSLATION_ERROR_THRESHOLD, 
@@ -40,6 +45,14 @@ impl Plugin for GameCommonPlugin {
                     force_replicate_error_count: PREDICTION_ERROR_COUNT_THRESHOLD 
                 },
             },
+            ServerTickEstimatePlugin{
+                config: ServerTickEstimateConfig{
+                    ping_interval: TICK_PING_INTERVAL,
+                    rtt_smoothing: TICK_RTT_SMOOTHING,
+                    correction: TICK_ESTIMATE_CORRECTION,
+                    snap_threshold: TICK_ESTIMATE_SNAP_THRESHOLD
+                }
+            },
             Rapier3DPlugin{
                 delta_time: PHYSICS_FIXED_TICK_DELTA,
                 substeps: PHYSICS_SUBSTEPS
@@ -60,16 +73,41 @@ impl Plugin for GameCommonPlugin {
             NetworkAngularVelocityPlugin::<NetworkAngularVelocity3D>::new(),
             
             ClientEventPlugin::<NetworkHit>::new(ChannelKind::Ordered),
-            ClientEventPlugin::<NetworkMovement2_5D>::new(ChannelKind::Unreliable)
+            ClientEventPlugin::<NetworkMovement2_5D>::redundant(
+                ChannelKind::Unreliable, 
+                MOVEMENT_REDUNDANCY
+            ),
+            MovementModelPlugin::<
+                PlayerMovementModel,
+                NetworkMovement2_5D
+            >::new(PlayerMovementModel{
+                base_speed: BASE_SPEED,
+                base_angular_speed: BASE_ANGULAR_SPEED,
+            }),
+            NetworkPredictedPlugin::<
+                NetworkJump,
+                NetworkMovement2_5D
+            >::new(),
+            InputQueuePlugin::<NetworkMovement2_5D>::new(
+                InputQueueConfig{
+                    target_depth: INPUT_QUEUE_TARGET_DEPTH,
+                    max_depth: INPUT_QUEUE_MAX_DEPTH,
+                    missing_input: MissingInput::Zero,
+                    feedback_interval: INPUT_QUEUE_FEEDBACK_INTERVAL
+                },
+                TimeDilationConfig{
+                    max_dilation: TIME_DILATION_MAX,
+                    gain: TIME_DILATION_GAIN
+                }
+            )
         ))
         .replicate::<PlayerPresentation>()
         .replicate::<Ball>()
-        .add_systems(FixedUpdate,(
-            ground_check_system,
-            update_character_controller_system,
-            apply_gravity_system
-        ).chain(
-        ).in_set(BootsetMain));
+        .add_systems(FixedUpdate, 
+            ground_check_system
+            .before(BootsetMovementModel)
+            .in_set(BootsetMain)
+        );
     }
 }
 
@@ -97,6 +135,36 @@ pub struct Jump {
     grounded: bool
 }
 
+/// vertical speed of jump and fall, predicted by owner
+#[derive(Component, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct NetworkJump {
+    pub power: f32
+}
+
+impl NetworkPredicted<NetworkMovement2_5D> for NetworkJump {
+    type Local = Jump;
+
+    #[inline]
+    fn from_local(local: &Jump) -> Self {
+        Self { power: local.power }
+    }
+
+    #[inline]
+    fn apply_local(&self, local: &mut Jump) {
+        local.power = self.power;
+    }
+
+    #[inline]
+    fn from_movement(movement: &NetworkMovement2_5D) -> Option<Self> {
+        Some(Self { power: movement.current_vertical_speed })
+    }
+
+    #[inline]
+    fn exceeds_threshold(&self, rhs: &Self) -> bool {
+        (self.power - rhs.power).abs() > VERTICAL_SPEED_ERROR_THRESHOLD
+    }
+}
+
 #[derive(Component, Default)]
 pub struct PlayerGroup {
     pub group: u8
@@ -121,12 +189,106 @@ impl RelevantGroup for PlayerGroup {
     }
 }
 
+#[derive(Clone, Copy)]
+pub enum PlayerAction {
+    Jump
+}
+
+impl NetworkAction for PlayerAction {
+    #[inline]
+    fn bit(self) -> u8 {
+        self as u8
+    }
+}
+
 #[derive(Resource, Clone)]
-pub struct PlayerMovementParams {
+pub struct PlayerMovementModel {
     pub base_speed: f32,
     pub base_angular_speed: f32,
 }
 
+impl MovementModel<NetworkMovement2_5D> for PlayerMovementModel {
+    type State = (
+        &'static mut Transform,
+        &'static mut KinematicCharacterController,
+        &'static mut Jump
+    );
+
+    fn step(
+        &self, 
+        state: &mut QueryItem<Self::State>, 
+        movement: &NetworkMovement2_5D, 
+        delta_time: f32
+    ) {
+        let (transform, cc, jump) = state;
+
+        if movement.rotation_axis != Vec2::ZERO {
+            let mut angle_delta = movement.rotation_axis.x;
+            angle_delta *= self.base_angular_speed * delta_time;
+            trace!("angle delta: {angle_delta}");
+
+            transform.rotate_y(-angle_delta.to_radians());
+        }
+
+        if movement.linear_axis != Vec2::ZERO {
+            let axis = Vec3::new(
+                movement.linear_axis.x, 
+                0.0, 
+                -movement.linear_axis.y
+            ).normalize();
+            let dir = transform.rotation * axis;
+            
+            let translation_delta = dir * self.base_speed * delta_time;
+            trace!("translation delta: {translation_delta}");
+    
+            match cc.translation {
+                Some(ref mut v) => *v += translation_delta,
+                None => cc.translation = Some(translation_delta)
+            }
+        }
+
+        if movement.actions.contains(PlayerAction::Jump) {
+            if jump.grounded {
+                jump.power = JUMP_POWER;    
+            }    
+        }
+
+        // gravity is a part of step, so replay matches server
+        if jump.grounded && jump.power != JUMP_POWER {
+            if jump.power != 0.0 {
+                jump.power = 0.0;
+            }
+            return;
+        }
+
+        let mass = cc.custom_mass.unwrap_or(1.0);
+        let g = GRAVITY * mass * delta_time;
+        let dy = jump.power * delta_time + g;
+        jump.power += g;
+        
+        match cc.translation {
+            Some(ref mut v) => v.y += dy,
+            None => cc.translation = Some(Vec3::new(0.0, dy, 0.0))
+        }
+    }
+
+    fn replay(
+        &self, 
+        state: &mut QueryItem<Self::State>, 
+        movement: &NetworkMovement2_5D, 
+        delta_time: f32
+    ) {
+        self.step(state, movement, delta_time);
+
+        // no collision while replaying, 
+        // controller moves transform directly
+        let (transform, cc, _) = state;
+        if let Some(t) = cc.translation.take() {
+            transform.translation += t;
+        }
+    }
+}
+
 #[derive(Event, Serialize, Deserialize, Clone)]
 pub struct NetworkHit {
     pub point: Vec3,
@@ -189,98 +351,6 @@ fn ground_check_system(
     }
 }
 
-fn update_character_controller_system(
-    mut query: Query<(
-        &mut Transform,
-        &mut KinematicCharacterController,
-        &mut Jump,
-        &mut EventCache<NetworkMovement2_5D>
-    )>,
-    params: Res<PlayerMovementParams>,
-    time: Res<Time<Fixed>>
-) {
-    for (
-        mut transform, 
-        mut cc, 
-        mut jump,
-        mut movements
-    ) in query.iter_mut() {
-        if movements.frontier_len() == 0 {
-            continue;
-        }
-
-        movements.sort_frontier_by_index();
-        let delta_time = time.delta_seconds();
-
-        for snap in movements.frontier_ref()
-        .iter() {
-            let movement = snap.event();
-
-            if movement.rotation_axis != Vec2::ZERO {
-                let mut angle_delta = movement.rotation_axis.x;
-                angle_delta *= params.base_angular_speed * delta_time;
-                trace!("angle delta: {angle_delta}");
-
-                transform.rotate_y(-angle_delta.to_radians());
-            }
-
-            if movement.linear_axis != Vec2::ZERO {
-                let axis = Vec3::new(
-                    movement.linear_axis.x, 
-                    0.0, 
-                    -movement.linear_axis.y
-                ).normalize();
-                let dir = transform.rotation * axis;
-                
-                let translation_delta = dir * params.base_speed * delta_time;
-                trace!("translation delta: {translation_delta}");
-        
-                match cc.translation {
-                    Some(ref mut v) => *v += translation_delta,
-                    None => cc.translation = Some(translation_delta)
-                }
-            }
-
-            if movement.bits & 0x01 != 0 {
-                if jump.grounded {
-                    jump.power = JUMP_POWER;    
-                }    
-            }
-        }
-
-        movements.cache();
-    }
-}
-
-fn apply_gravity_system(
-    mut query: Query<(
-        &mut KinematicCharacterController, 
-        &mut Jump
-    )>,
-    time: Res<Time<Fixed>>
-) {
-    for (mut cc, mut jump) in query.iter_mut() {
-        if jump.grounded && jump.power != JUMP_POWER {
-            if jump.power != 0.0 {
-                jump.power = 0.0;
-            }
-            
-            continue;   
-        }
-
-        let delta_time = time.delta_seconds();
-        let mass = cc.custom_mass.unwrap_or(1.0);
-        let g = GRAVITY * mass * delta_time;
-        let dy = jump.power * delta_time + g;
-        jump.power += g;
-        
-        match cc.translation {
-            Some(ref mut v) => v.y += dy,
-            None => cc.translation = Some(Vec3::new(0.0, dy, 0.0))
-        }
-    }
-}
-
 // pub fn handle_netcode_transport_error(mut errors: EventReader<NetcodeTransportError>) {
 //     for e in errors.read() {
 //         panic!("transport error: {e}")
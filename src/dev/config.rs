@@ -13,7 +13,13 @@ pub const DEV_SERVER_TICK_RATE: f32 = /*30.0;*/ 20.0; //10.0;
 pub const DEV_SERVER_TICK_DELTA: f32 = 1.0 / DEV_SERVER_TICK_RATE;
 pub const DEV_NETWORK_TICK_RATE: u16 = /*30;*/ /*20;*/ 10; //1;
 pub const DEV_NETWORK_TICK_DELTA: f32 = 1.0 / (DEV_NETWORK_TICK_RATE as f32); 
-pub const DEV_NETWORK_TICK_DELTA64: f64 = 1.0 / (DEV_NETWORK_TICK_RATE as f64); 
+pub const DEV_NETWORK_TICK_DELTA64: f64 = 1.0 / (DEV_NETWORK_TICK_RATE as f64);
+pub const MIN_RENDER_DELAY: f64 = DEV_NETWORK_TICK_DELTA64;
+pub const MAX_RENDER_DELAY: f64 = DEV_NETWORK_TICK_DELTA64 * 4.0;
+pub const JITTER_MULTIPLIER: f64 = 2.0;
+pub const JITTER_SMOOTHING: f64 = 0.1;
+pub const EXTRAPOLATION_MAX_TIME: f32 = DEV_NETWORK_TICK_DELTA * 2.0;
+pub const EXTRAPOLATION_BLEND_HALF_LIFE: f32 = 0.05; 
 
 pub const DEV_SERVER_LISTEN_PORT: u16 = 5000;
 pub const DEV_SERVER_MAX_CLIENTS: usize = 10;
@@ -28,11 +34,32 @@ pub const NO_CACHE: usize = 0;
 
 pub const TRANSLATION_REPLICATION_THRESHOLD: f32 = 0.001;
 pub const ROTATION_REPLICATION_THRESHOLD: f32 = 0.01;
+pub const SCALE_REPLICATION_THRESHOLD: f32 = 0.01;
+pub const TELEPORT_THRESHOLD: f32 = 5.0;
+pub const KEYFRAME_INTERVAL: u32 = DEV_NETWORK_TICK_RATE as u32;
+pub const KEYFRAME_REST_TICKS: u32 = 3;
 
 pub const TRANSLATION_ERROR_THRESHOLD: f32 = 1.0;
 pub const ROTATION_ERROR_THRESHOLD: f32 = 10.0;
+pub const VERTICAL_SPEED_ERROR_THRESHOLD: f32 = 1.0;
 pub const PREDICTION_ERROR_COUNT_THRESHOLD: u32 = 10;
 
+pub const INPUT_QUEUE_TARGET_DEPTH: usize = 2;
+pub const INPUT_QUEUE_MAX_DEPTH: usize = 8;
+pub const TICK_PING_INTERVAL: f64 = 0.5;
+pub const TICK_RTT_SMOOTHING: f64 = 0.1;
+pub const TICK_ESTIMATE_CORRECTION: f64 = 0.2;
+pub const TICK_ESTIMATE_SNAP_THRESHOLD: f64 = 4.0;
+pub const MOVEMENT_REDUNDANCY: usize = 4;
+pub const INPUT_QUEUE_FEEDBACK_INTERVAL: u32 = 32;
+pub const TIME_DILATION_MAX: f64 = 0.1;
+pub const TIME_DILATION_GAIN: f64 = 0.02;
+
+pub const SMOOTHING_TRANSLATION_HALF_LIFE: f32 = 0.05;
+pub const SMOOTHING_ROTATION_HALF_LIFE: f32 = 0.05;
+pub const SMOOTHING_MAX_TRANSLATION_ERROR: f32 = 5.0;
+pub const SMOOTHING_MAX_ROTATION_ERROR: f32 = 90.0;
+
 pub const DISTANCE_CULLING_THREASHOLD: f32 = 35.0;
 
 pub fn get_dev_protocol_id() -> u64 {
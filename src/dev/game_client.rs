@@ -25,6 +25,31 @@ pub struct GameClientPlugin;
 impl Plugin for GameClientPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(GameCommonPlugin)
+        .add_plugins(PredictionSmoothingPlugin{
+            config: SmoothingConfig{
+                translation_half_life: SMOOTHING_TRANSLATION_HALF_LIFE,
+                rotation_half_life: SMOOTHING_ROTATION_HALF_LIFE,
+                max_translation_error: SMOOTHING_MAX_TRANSLATION_ERROR,
+                max_rotation_error: SMOOTHING_MAX_ROTATION_ERROR
+            }
+        })
+        .add_plugins((
+            GhostDespawnPlugin,
+            TranslationExtrapolationPlugin::<
+                NetworkTranslation3D,
+                NetworkLinearVelocity3D
+            >::new(ExtrapolationConfig{
+                max_time: EXTRAPOLATION_MAX_TIME,
+                blend_half_life: EXTRAPOLATION_BLEND_HALF_LIFE
+            }),
+            RotationExtrapolationPlugin::<
+                NetworkEuler,
+                NetworkAngularVelocity3D
+            >::new(ExtrapolationConfig{
+                max_time: EXTRAPOLATION_MAX_TIME,
+                blend_half_life: EXTRAPOLATION_BLEND_HALF_LIFE
+            })
+        ))
         .insert_resource(KeyboardInputActionMap{
             movement_up: KeyCode::KeyW,
             movement_left: KeyCode::KeyA,
@@ -36,7 +61,7 @@ impl Plugin for GameClientPlugin {
             fire: MouseButton::Left
         })
         .insert_resource(EntityPlayerMap::default())
-        .add_event::<Action>()
+        .insert_resource(InputState::default())
         .add_event::<Fire>()
         .add_systems(Startup, (
             setup_light,
@@ -49,33 +74,24 @@ impl Plugin for GameClientPlugin {
             //handle_renetcode_error,
             handle_player_spawned,
             handle_input, 
-            handle_action,
             handle_fire,
             draw_gizmos_system
-        ).chain());
+        ).chain())
+        .add_systems(FixedUpdate, 
+            sample_movement_system
+            .before(BootsetMovementModel)
+            .in_set(BootsetMain)
+        );
     }
 }
 
-#[derive(Event, Default)]
-struct Action {
-    pub movement_vec: Vec2,
-    pub rotation_vec: Vec2,
-    pub has_jump: bool,
-    pub has_fire: bool 
-}
-
-impl Action {
-    #[inline]
-    pub fn has_movement(&self) -> bool {
-        self.movement_vec != Vec2::ZERO 
-        || self.rotation_vec != Vec2::ZERO
-        || self.has_jump
-    }
-    
-    #[inline]
-    pub fn has_action(&self) -> bool {
-        self.has_movement() || self.has_fire 
-    }
+/// input accumulated over render frames until next fixed step
+#[derive(Resource, Default)]
+struct InputState {
+    movement_vec: Vec2,
+    rotation_vec: Vec2,
+    has_jump: bool,
+    index: u64
 }
 
 #[derive(Event)]
@@ -87,80 +103,70 @@ fn handle_input(
     mut mouse_motion: EventReader<MouseMotion>,
     keyboard_action_map: Res<KeyboardInputActionMap>,
     mouse_action_map: Res<MouseInputActionMap>,
-    mut actions: EventWriter<Action> 
+    mut input: ResMut<InputState>,
+    mut fires: EventWriter<Fire>
 ) {
-    let mut action = Action::default();
+    let mut movement_vec = Vec2::ZERO;
     if keyboard.pressed(keyboard_action_map.movement_up) {
-        action.movement_vec.y += 1.0
+        movement_vec.y += 1.0
     } 
     if keyboard.pressed(keyboard_action_map.movement_down) {
-        action.movement_vec.y -= 1.0
+        movement_vec.y -= 1.0
     }
     if keyboard.pressed(keyboard_action_map.movement_right) {
-        action.movement_vec.x += 1.0
+        movement_vec.x += 1.0
     }
     if keyboard.pressed(keyboard_action_map.movement_left) {
-        action.movement_vec.x -= 1.0
+        movement_vec.x -= 1.0
     }
+    input.movement_vec = movement_vec;
 
     if keyboard.just_pressed(keyboard_action_map.jump) {
-        action.has_jump = true;
-    }
-
-    if mouse_button.just_pressed(mouse_action_map.fire) {
-        action.has_fire = true;
+        input.has_jump = true;
     }
 
     for e in mouse_motion.read() {
-        action.rotation_vec += e.delta;
+        input.rotation_vec += e.delta;
     }
 
-    if action.has_action() {
-        actions.send(action);
+    if mouse_button.just_pressed(mouse_action_map.fire) {
+        fires.send(Fire);
     }
 } 
 
-fn handle_action(
-    query: Query<&Transform, With<Owning>>,
-    mut actions: EventReader<Action>,
+// one movement per fixed step, so time dilation changes send rate
+fn sample_movement_system(
+    query: Query<(&Transform, &Jump), With<Owning>>,
+    mut input: ResMut<InputState>,
     mut movements: EventWriter<NetworkMovement2_5D>,
-    latest_confirmed: Res<LatestConfirmedTick>,
-    mut fires: EventWriter<Fire>
+    tick_estimate: Res<ServerTickEstimate>
 ) {
-    let Ok(transform) = query.get_single() else {
+    let Ok((transform, jump)) = query.get_single() else {
         return;
     };
 
-    let tick = latest_confirmed.get()
-    .get();
+    let mut actions = ActionFlags::default();
+    actions.set(PlayerAction::Jump, input.has_jump);
 
-    for (a, event_id) in actions.read_with_id() {
-        if a.has_movement() {
-            let mut bits = 0;
-            if a.has_jump {
-                bits |= 0x01;
-            }
-
-            let current_translation = transform.translation;
-            let current_yaw = transform.rotation.to_euler(EulerRot::YXZ)
-            .0
-            .to_degrees();  
+    let current_yaw = transform.rotation.to_euler(EulerRot::YXZ)
+    .0
+    .to_degrees();  
 
-            movements.send(NetworkMovement2_5D{
-                current_translation,
-                current_angle: current_yaw,
-                linear_axis: a.movement_vec,
-                rotation_axis: a.rotation_vec,
-                bits,
-                index: event_id.id as u64,
-                tick
-            });
-        }
+    movements.send(NetworkMovement2_5D{
+        current_translation: transform.translation,
+        current_angle: current_yaw,
+        current_vertical_speed: NetworkJump::from_local(jump).power,
+        linear_axis: input.movement_vec,
+        rotation_axis: input.rotation_vec,
+        actions,
+        index: input.index,
+        tick: tick_estimate.tick()
+    });
 
-        if a.has_fire {
-            fires.send(Fire);
-        }
-    }
+    // pressed ones and pointer delta are consumed by this step
+    input.index += 1;
+    input.rotation_vec = Vec2::ZERO;
+    input.has_jump = false;
 }
 
 fn handle_player_spawned(
@@ -179,7 +185,8 @@ fn handle_player_spawned(
     >,
     mut entity_player_map: ResMut<EntityPlayerMap>,
     axis: Res<TransformAxis>,
-    replicon_client: Res<RepliconClient>
+    replicon_client: Res<RepliconClient>,
+    clock: Res<NetworkClock>
 ) {
     for (
         e, net_e, 
@@ -197,15 +204,22 @@ fn handle_player_spawned(
 
         let tick = confirmed_tick.last_tick()
         .get();
+        let now = clock.now();
+
+        // mesh is a child, so prediction smoothing can offset it
+        let visual = commands.spawn(PbrBundle{
+            mesh: meshes.add(Mesh::from(Capsule3d::new(
+                CHARACTER_RADIUS, 
+                CHARACTER_HALF_HIGHT * 2.0
+            ))),
+            material: materials.add(presentation.color),
+            ..default()
+        })
+        .id();
 
         let entity = commands.entity(e)
         .insert((
-            PbrBundle{
-                mesh: meshes.add(Mesh::from(Capsule3d::new(
-                    CHARACTER_RADIUS, 
-                    CHARACTER_HALF_HIGHT * 2.0
-                ))),
-                material: materials.add(presentation.color),
+            SpatialBundle{
                 transform: Transform{
                     translation: net_trans.to_vec3(axis.translation),
                     rotation: net_rot.to_quat(axis.rotation),
@@ -216,18 +230,23 @@ fn handle_player_spawned(
             ComponentCache::with_init(
                 *net_trans, 
                 tick, 
+                now,
                 SMALL_CACHE_SIZE
-            ).expect("sytem time looks earlier than unix epoch"),
+            ).expect("could not insert initial snapshot"),
             ComponentCache::with_init(
                 *net_rot, 
                 tick, 
+                now,
                 SMALL_CACHE_SIZE
-            ).expect("sytem time looks earlier than unix epoch")
+            ).expect("could not insert initial snapshot")
         ))
+        .add_child(visual)
         .id();
 
         let client_id = net_e.client_id();
         if this_client_id == client_id {
+            commands.entity(visual)
+            .insert(SmoothingVisual::default());
             commands.entity(e)
             .insert((
                 Owning,
@@ -238,14 +257,18 @@ fn handle_player_spawned(
                     CHARACTER_MASS
                 ),
                 Jump::default(),
-                EventCache::<NetworkMovement2_5D>::with_capacity(MEDIUM_CACHE_SIZE)
+                EventCache::<NetworkMovement2_5D>::with_capacity(MEDIUM_CACHE_SIZE),
+                PredictionSmoothing::default()
             ));
             info!("this is the owner of the spawned entity");
         } else {
             commands.entity(e)
-            .insert(CharacterControllerBundle::replica(
-                CHARACTER_HALF_HIGHT,
-                CHARACTER_RADIUS
+            .insert((
+                CharacterControllerBundle::replica(
+                    CHARACTER_HALF_HIGHT,
+                    CHARACTER_RADIUS
+                ),
+                GhostDespawn::default()
             ));
         }
         
@@ -260,7 +283,10 @@ fn handle_fire(
     query: Query<(Entity, &Transform), With<Owning>>,
     rapier: Res<RapierContext>,
     entity_player_map: Res<EntityPlayerMap>,
-    latest_confirmed: Res<LatestConfirmedTick>,
+    render_delay: Res<RenderDelay>,
+    timeline: Res<NetworkTimeline>,
+    interpolation_config: Res<InterpolationConfig>,
+    time: Res<Time<Real>>,
     mut fires: EventReader<Fire>,
     mut hits: EventWriter<NetworkHit>
 ) {
@@ -287,9 +313,16 @@ fn handle_fire(
             None => continue
         };
     
-        let tick = latest_confirmed
-        .get()
-        .get();
+        // target is rendered between snapshots around this tick,
+        // server checks this tick and the one before
+        let Some(render_tick) = timeline.tick_at(
+            time.elapsed_seconds_f64() - render_delay.delay(), 
+            &interpolation_config
+        ) else {
+            warn!("network timeline is not ready, skipping hit");
+            continue;
+        };
+        let tick = render_tick.ceil().max(0.0) as u32;
     
         info!(
             "requesting hit: client: {} point: {} at tick: {}", 
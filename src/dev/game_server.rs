@@ -90,10 +90,12 @@ fn handle_player_entity_event(
     mut events: EventReader<PlayerEntityEvent>,
     mut start_lines: ResMut<PlayerStartLines>,
     server_tick: Res<ServerTick>,
+    clock: Res<NetworkClock>
 ) {
     for e in events.read() {
         if let PlayerEntityEvent::Spawned { client_id, entity } = e {
             let tick = server_tick.get();
+            let now = clock.now();
             let group = PlayerGroup::default();//random();
             let player_start = start_lines.next(0)
             .expect("missing player start lines initialization");
@@ -115,18 +117,28 @@ fn handle_player_entity_event(
                     CHARACTER_MASS
                 ),
                 Jump::default(),
+                NetworkPredictedBundle::new(
+                    NetworkJump::default(),
+                    tick,
+                    now,
+                    LARGE_CACHE_SIZE
+                ).expect("could not insert initial snapshot"),
+                NetworkTeleport::default(),
+                KeyframeReplication::new(KEYFRAME_INTERVAL, KEYFRAME_REST_TICKS),
                 NetworCharacterkTranslationBundle::<NetworkTranslation3D>::new(
                     player_start.translation,
                     TranslationAxis::Default, 
                     tick, 
+                    now,
                     LARGE_CACHE_SIZE
-                ).expect("sytem time looks earlier than unix epoch"),
+                ).expect("could not insert initial snapshot"),
                 NetworkCharacterRotationBundle::<NetworkAngleDegrees>::new(
                     Quat::IDENTITY, 
                     RotationAxis::Z,
                     tick, 
+                    now,
                     LARGE_CACHE_SIZE
-                ).expect("sytem time looks earlier than unix epoch"),
+                ).expect("could not insert initial snapshot"),
                 EventCache::<NetworkMovement2_5D>::with_capacity(
                     MEDIUM_CACHE_SIZE
                 ),
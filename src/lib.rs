@@ -19,8 +19,15 @@ pub mod prelude {
 }
 
 use std::marker::PhantomData;
-use bevy::prelude::*;
-use bevy_replicon::prelude::*;
+use bevy::{
+    prelude::*,
+    transform::TransformSystem
+};
+use bevy_replicon::{
+    prelude::*,
+    core::replication_fns::ReplicationFns,
+    server::server_tick::ServerTick
+};
 use prelude::*;
 
 pub struct NetworkBootPlugin {
@@ -32,6 +39,10 @@ pub struct NetworkBootPlugin {
 
 impl Plugin for NetworkBootPlugin {
     fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<NetworkClock>() {
+            app.insert_resource(NetworkClock::monotonic());
+        }
+
         app.insert_resource(self.transform_axis.clone())
         .insert_resource(self.replication_config.clone())
         .insert_resource(self.interpolation_config.clone())
@@ -40,6 +51,10 @@ impl Plugin for NetworkBootPlugin {
             BootsetMain
             .before(BEFORE_PHYSICS_SET)
         )
+        .configure_sets(FixedUpdate, 
+            BootsetMovementModel
+            .in_set(BootsetMain)
+        )
         .configure_sets(PreUpdate, 
             ClientBootSet::UnboxReplication
             .after(ClientSet::Receive)
@@ -48,6 +63,10 @@ impl Plugin for NetworkBootPlugin {
             ClientBootSet::ApplyReplication
             .after(ClientBootSet::UnboxReplication)
         )
+        .configure_sets(PreUpdate, 
+            ClientBootSet::Reconcile
+            .after(ClientBootSet::ApplyReplication)
+        )
         .configure_sets(PostUpdate, 
             ClientBootSet::Cache
             .before(ClientSet::Send)
@@ -80,14 +99,74 @@ impl Plugin for NetworkBootPlugin {
             ServerBootSet::ApplyLocalChange
             .before(ServerBootSet::Cache)
         )
-        .replicate::<NetworkEntity>();
+        .replicate::<NetworkEntity>()
+        .replicate::<NetworkTeleport>();
+
+        if app.world().contains_resource::<RepliconServer>() {
+            app.add_systems(PostUpdate, (
+                detect_teleport_system
+                .in_set(ServerBootSet::ApplyLocalChange),
+                keyframe_rest_system
+                .before(ServerBootSet::ApplyLocalChange)
+            ));
+        }
 
         if app.world().contains_resource::<RepliconClient>() {
             app.insert_resource(LatestConfirmedTick::default())
+            .insert_resource(RenderDelay::default())
+            .insert_resource(NetworkTimeline::default())
+            .add_systems(PreUpdate, (
+                latest_confirmed_tick_system,
+                network_timeline_system
+            ).chain().in_set(ClientBootSet::UnboxReplication));
+        }
+    }
+}
+
+pub struct GhostDespawnPlugin;
+
+impl Plugin for GhostDespawnPlugin {
+    fn build(&self, app: &mut App) {
+        if app.world().contains_resource::<RepliconClient>() {
+            app.world_mut()
+            .resource_mut::<ReplicationFns>()
+            .despawn = ghost_despawn_fn;
+
+            app.add_event::<GhostSpawned>()
+            .add_systems(PostUpdate, ghost_system);
+        } else {
+            panic!("could not find replicon client");
+        }
+    }
+}
+
+pub struct ServerTickEstimatePlugin {
+    pub config: ServerTickEstimateConfig
+}
+
+impl Plugin for ServerTickEstimatePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_client_event::<TickPing>(ChannelKind::Unreliable)
+        .add_server_event::<TickPong>(ChannelKind::Unreliable);
+
+        if app.world().contains_resource::<RepliconServer>() {
+            app.add_systems(PreUpdate, 
+                server_tick_pong_system
+                .in_set(ServerBootSet::UnboxEvent)
+            );
+        } else if app.world().contains_resource::<RepliconClient>() {
+            app.insert_resource(self.config.clone())
+            .insert_resource(ServerTickEstimate::default())
             .add_systems(PreUpdate, 
-                latest_confirmed_tick_system
-                .in_set(ClientBootSet::UnboxReplication)
+                client_tick_estimate_system
+                .in_set(ClientBootSet::ApplyReplication)
+            )
+            .add_systems(PostUpdate, 
+                client_tick_ping_system
+                .before(ClientSet::Send)
             );
+        } else {
+            panic!("could not find replicon server nor client");
         }
     }
 }
@@ -129,15 +208,17 @@ where T: NetworkTranslation {
         .add_plugins(ComponentCachePlugin::<T>::new());
 
         if app.world().contains_resource::<RepliconServer>() {
-            app.add_systems(PostUpdate, 
-                apply_transform_translation_system::<T>
-                .in_set(ServerBootSet::ApplyLocalChange)
-            );
+            app.add_systems(PostUpdate, (
+                apply_transform_translation_system::<T>,
+                warn_translation_range_system::<T>
+            ).in_set(ServerBootSet::ApplyLocalChange));
         } else if app.world().contains_resource::<RepliconClient>() {
-            app.add_systems(PreUpdate, 
+            app.add_systems(PreUpdate, (
+                warm_translation_system::<T>,
+                teleport_translation_system::<T>,
                 apply_network_translation_system::<T>
-                .in_set(ClientBootSet::ApplyReplication)
-            )
+            ).chain(
+            ).in_set(ClientBootSet::ApplyReplication))
             .add_systems(PostUpdate, 
                 cache_translation_system::<T>
                 .in_set(ClientBootSet::Cache)
@@ -171,12 +252,300 @@ where R: NetworkRotation {
                 .in_set(ServerBootSet::ApplyLocalChange)
             );
         } else if app.world().contains_resource::<RepliconClient>() {
+            app.add_systems(PreUpdate, (
+                warm_rotation_system::<R>,
+                teleport_rotation_system::<R>,
+                apply_network_rotation_system::<R>
+            ).chain(
+            ).in_set(ClientBootSet::ApplyReplication))
+            .add_systems(PostUpdate, 
+                cache_rotation_system::<R>
+                .in_set(ClientBootSet::Cache)
+            );
+        } else {
+            panic!("could not find replicon server nor client");
+        }
+    }
+}
+
+pub struct DeltaReplicationPlugin<C: NetworkDelta> {
+    pub config: DeltaConfig<C>
+}
+
+impl<C: NetworkDelta> DeltaReplicationPlugin<C> {
+    #[inline]
+    pub fn new(config: DeltaConfig<C>) -> Self {
+        Self { config }
+    }
+}
+
+impl<C: NetworkDelta> Plugin for DeltaReplicationPlugin<C> {
+    fn build(&self, app: &mut App) {
+        app.add_mapped_server_event::<DeltaReplication<C>>(ChannelKind::Unreliable)
+        .add_mapped_client_event::<AcknowledgeDelta<C>>(ChannelKind::Unreliable)
+        .insert_resource(self.config.clone());
+
+        if app.world().contains_resource::<RepliconServer>() {
+            // one snapshot per server tick, the one that is sent
+            app.init_resource::<DeltaAcks<C>>()
+            .add_systems(PreUpdate, 
+                server_delta_ack_system::<C>
+                .in_set(ServerBootSet::UnboxEvent)
+            )
+            .add_systems(PostUpdate, (
+                server_populate_component_cache::<C>,
+                server_send_delta_system::<C>
+            ).chain(
+            ).after(ServerPlugin::increment_tick)
+            .run_if(resource_changed::<ServerTick>)
+            .in_set(ServerBootSet::Cache));
+        } else if app.world().contains_resource::<RepliconClient>() {
+            // snapshots are inserted by received tick, not by confirm history
             app.add_systems(PreUpdate, 
+                client_receive_delta_system::<C>
+                .in_set(ClientBootSet::UnboxReplication)
+            );
+        } else {
+            panic!("could not find replicon server nor client");
+        }
+    }
+}
+
+/// translation sent to each client as offset from its last acknowledged snapshot,
+/// T is not replicated and inserted on clients with first absolute value
+pub struct DeltaTranslationPlugin<T>
+where T: NetworkTranslation + NetworkDelta {
+    pub config: DeltaConfig<T>
+}
+
+impl<T> DeltaTranslationPlugin<T>
+where T: NetworkTranslation + NetworkDelta {
+    #[inline]
+    pub fn new(config: DeltaConfig<T>) -> Self {
+        Self { config }
+    }
+}
+
+impl<T> Plugin for DeltaTranslationPlugin<T>
+where T: NetworkTranslation + NetworkDelta {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(DeltaReplicationPlugin::new(self.config.clone()));
+
+        if app.world().contains_resource::<RepliconServer>() {
+            app.add_systems(PostUpdate, (
+                apply_transform_translation_system::<T>,
+                warn_translation_range_system::<T>
+            ).in_set(ServerBootSet::ApplyLocalChange));
+        } else if app.world().contains_resource::<RepliconClient>() {
+            app.add_systems(PreUpdate, (
+                warm_translation_system::<T>,
+                teleport_translation_system::<T>,
+                apply_network_translation_system::<T>
+            ).chain(
+            ).in_set(ClientBootSet::ApplyReplication))
+            .add_systems(PostUpdate, 
+                cache_translation_system::<T>
+                .in_set(ClientBootSet::Cache)
+            );
+        } else {
+            panic!("could not find replicon server nor client");
+        }
+    }
+}
+
+/// rotation counterpart of DeltaTranslationPlugin
+pub struct DeltaRotationPlugin<R>
+where R: NetworkRotation + NetworkDelta {
+    pub config: DeltaConfig<R>
+}
+
+impl<R> DeltaRotationPlugin<R>
+where R: NetworkRotation + NetworkDelta {
+    #[inline]
+    pub fn new(config: DeltaConfig<R>) -> Self {
+        Self { config }
+    }
+}
+
+impl<R> Plugin for DeltaRotationPlugin<R>
+where R: NetworkRotation + NetworkDelta {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(DeltaReplicationPlugin::new(self.config.clone()));
+
+        if app.world().contains_resource::<RepliconServer>() {
+            app.add_systems(PostUpdate, 
+                apply_transform_rotation_system::<R>
+                .in_set(ServerBootSet::ApplyLocalChange)
+            );
+        } else if app.world().contains_resource::<RepliconClient>() {
+            app.add_systems(PreUpdate, (
+                warm_rotation_system::<R>,
+                teleport_rotation_system::<R>,
                 apply_network_rotation_system::<R>
+            ).chain(
+            ).in_set(ClientBootSet::ApplyReplication))
+            .add_systems(PostUpdate, 
+                cache_rotation_system::<R>
+                .in_set(ClientBootSet::Cache)
+            );
+        } else {
+            panic!("could not find replicon server nor client");
+        }
+    }
+}
+
+pub struct ReconciliationPlugin<E>(PhantomData<E>)
+where E: NetworkMovement;
+
+impl<E> ReconciliationPlugin<E>
+where E: NetworkMovement {
+    #[inline]
+    pub fn new() -> Self {
+        Self(PhantomData::<E>)
+    }
+}
+
+impl<E> Plugin for ReconciliationPlugin<E>
+where E: NetworkMovement {
+    fn build(&self, app: &mut App) {
+        if app.world().contains_resource::<RepliconClient>() {
+            app.add_systems(PreUpdate, (
+                insert_reconciliation_system::<E>
+                .in_set(ClientBootSet::UnboxReplication),
+                reconcile_system::<E>
+                .in_set(ClientBootSet::Reconcile)
+            ));
+        } else {
+            panic!("could not find replicon client");
+        }
+    }
+}
+
+pub struct InputAckPlugin<E>(PhantomData<E>)
+where E: NetworkMovement;
+
+impl<E> InputAckPlugin<E>
+where E: NetworkMovement {
+    #[inline]
+    pub fn new() -> Self {
+        Self(PhantomData::<E>)
+    }
+}
+
+impl<E> Plugin for InputAckPlugin<E>
+where E: NetworkMovement {
+    fn build(&self, app: &mut App) {
+        app.add_server_event::<AcknowledgeInput<E>>(ChannelKind::Unreliable);
+
+        if app.world().contains_resource::<RepliconServer>() {
+            app.add_systems(PostUpdate, (
+                insert_input_ack_system::<E>,
+                server_input_ack_system::<E>
+                .after(ServerPlugin::increment_tick)
+                .run_if(resource_changed::<ServerTick>)
+            ).chain(
+            ).in_set(ServerBootSet::Cache));
+        } else if app.world().contains_resource::<RepliconClient>() {
+            app.add_systems(PreUpdate, (
+                insert_input_ack_system::<E>
+                .in_set(ClientBootSet::UnboxReplication),
+                client_input_ack_system::<E>
                 .in_set(ClientBootSet::ApplyReplication)
+            ));
+        } else {
+            panic!("could not find replicon server nor client");
+        }
+    }
+}
+
+pub struct PredictionSmoothingPlugin {
+    pub config: SmoothingConfig
+}
+
+impl Plugin for PredictionSmoothingPlugin {
+    fn build(&self, app: &mut App) {
+        if app.world().contains_resource::<RepliconClient>() {
+            app.insert_resource(self.config.clone())
+            .add_systems(PreUpdate, 
+                resolve_smoothing_system
+                .after(ClientBootSet::Reconcile)
             )
             .add_systems(PostUpdate, 
-                cache_rotation_system::<R>
+                apply_smoothing_system
+                .before(TransformSystem::TransformPropagate)
+            );
+        } else {
+            panic!("could not find replicon client");
+        }
+    }
+}
+
+pub struct NetworkScalePlugin<S>(PhantomData<S>)
+where S: NetworkScale;
+
+impl<S> NetworkScalePlugin<S>
+where S: NetworkScale {
+    #[inline]
+    pub fn new() -> Self {
+        Self(PhantomData::<S>)
+    }
+}
+
+impl<S> Plugin for NetworkScalePlugin<S>
+where S: NetworkScale {
+    fn build(&self, app: &mut App) {
+        app.replicate::<S>()
+        .add_plugins(ComponentCachePlugin::<S>::new());
+
+        if app.world().contains_resource::<RepliconServer>() {
+            app.add_systems(PostUpdate, 
+                apply_transform_scale_system::<S>
+                .in_set(ServerBootSet::ApplyLocalChange)
+            );
+        } else if app.world().contains_resource::<RepliconClient>() {
+            app.add_systems(PreUpdate, 
+                apply_network_scale_system::<S>
+                .in_set(ClientBootSet::ApplyReplication)
+            )
+            .add_systems(PostUpdate, 
+                cache_scale_system::<S>
+                .in_set(ClientBootSet::Cache)
+            );
+        } else {
+            panic!("could not find replicon server nor client");
+        }
+    }
+}
+
+pub struct NetworkTransformPlugin<N>(PhantomData<N>)
+where N: NetworkTransform;
+
+impl<N> NetworkTransformPlugin<N>
+where N: NetworkTransform {
+    #[inline]
+    pub fn new() -> Self {
+        Self(PhantomData::<N>)
+    }
+}
+
+impl<N> Plugin for NetworkTransformPlugin<N>
+where N: NetworkTransform {
+    fn build(&self, app: &mut App) {
+        app.replicate::<N>()
+        .add_plugins(ComponentCachePlugin::<N>::new());
+
+        if app.world().contains_resource::<RepliconServer>() {
+            app.add_systems(PostUpdate, 
+                apply_transform_network_system::<N>
+                .in_set(ServerBootSet::ApplyLocalChange)
+            );
+        } else if app.world().contains_resource::<RepliconClient>() {
+            app.add_systems(PreUpdate, 
+                apply_network_transform_system::<N>
+                .in_set(ClientBootSet::ApplyReplication)
+            )
+            .add_systems(PostUpdate, 
+                cache_network_transform_system::<N>
                 .in_set(ClientBootSet::Cache)
             );
         } else {
@@ -185,6 +554,45 @@ where R: NetworkRotation {
     }
 }
 
+pub struct NetworkInterpolatePlugin<C>
+where C: NetworkInterpolate {
+    pub cache_size: usize,
+    phantom: PhantomData<C>
+}
+
+impl<C> NetworkInterpolatePlugin<C>
+where C: NetworkInterpolate {
+    #[inline]
+    pub fn new(cache_size: usize) -> Self {
+        Self { 
+            cache_size, 
+            phantom: PhantomData::<C> 
+        }
+    }
+}
+
+impl<C> Plugin for NetworkInterpolatePlugin<C>
+where C: NetworkInterpolate {
+    fn build(&self, app: &mut App) {
+        // server writes C directly and only needs replication,
+        // cache and display component exist on clients only
+        app.replicate::<C>();
+
+        if app.world().contains_resource::<RepliconClient>() {
+            app.add_plugins(ComponentCachePlugin::<C>::new())
+            .insert_resource(InterpolateCacheSize::<C>::new(self.cache_size))
+            .add_systems(PreUpdate, (
+                insert_interpolate_system::<C>
+                .in_set(ClientBootSet::UnboxReplication),
+                interpolate_display_system::<C>
+                .in_set(ClientBootSet::ApplyReplication)
+            ));
+        } else if !app.world().contains_resource::<RepliconServer>() {
+            panic!("could not find replicon server nor client");
+        }
+    }
+}
+
 pub struct NetworkCharacterTranslationPlugin<T, E>(PhantomData<T>, PhantomData<E>)
 where
 T: NetworkTranslation,
@@ -208,6 +616,10 @@ E: NetworkMovement {
         app.add_plugins(NetworkTranslationPlugin::<T>::new())
         .add_server_event::<ForceReplicateTranslation<T>>(ChannelKind::Ordered);
 
+        if !app.is_plugin_added::<InputAckPlugin<E>>() {
+            app.add_plugins(InputAckPlugin::<E>::new());
+        }
+
         if app.world().contains_resource::<RepliconServer>() {
             app.add_systems(PreUpdate,
                 correct_translation_error_system::<T, E>
@@ -217,6 +629,10 @@ E: NetworkMovement {
             app.add_systems(PreUpdate, (
                 handle_correct_translation::<T, E>,
             ).in_set(ClientBootSet::ApplyReplication));
+
+            if !app.is_plugin_added::<ReconciliationPlugin<E>>() {
+                app.add_plugins(ReconciliationPlugin::<E>::new());
+            }
         } else {
             panic!("could not find replicon server nor client");
         }
@@ -245,6 +661,10 @@ E: NetworkMovement {
     fn build(&self, app: &mut App) {
         app.add_plugins(NetworkRotationPlugin::<R>::new())
         .add_server_event::<ForceReplicateRotation<R>>(ChannelKind::Ordered);
+
+        if !app.is_plugin_added::<InputAckPlugin<E>>() {
+            app.add_plugins(InputAckPlugin::<E>::new());
+        }
     
         if app.world().contains_resource::<RepliconServer>() {
             app.add_systems(FixedPreUpdate, 
@@ -255,6 +675,160 @@ E: NetworkMovement {
             app.add_systems(PreUpdate, (
                 handle_correct_rotation::<R, E>
             ).in_set(ClientBootSet::ApplyReplication));
+
+            if !app.is_plugin_added::<ReconciliationPlugin<E>>() {
+                app.add_plugins(ReconciliationPlugin::<E>::new());
+            }
+        } else {
+            panic!("could not find replicon server nor client");
+        }
+    }
+}
+
+pub struct NetworkPredictedPlugin<C, E>(PhantomData<C>, PhantomData<E>)
+where 
+C: NetworkPredicted<E>,
+E: NetworkMovement;
+
+impl<C, E> NetworkPredictedPlugin<C, E>
+where 
+C: NetworkPredicted<E>,
+E: NetworkMovement {
+    #[inline]
+    pub fn new() -> Self {
+        Self(PhantomData::<C>, PhantomData::<E>)
+    }
+}
+
+impl<C, E> Plugin for NetworkPredictedPlugin<C, E>
+where 
+C: NetworkPredicted<E>,
+E: NetworkMovement {
+    fn build(&self, app: &mut App) {
+        app.replicate::<C>()
+        .add_plugins(ComponentCachePlugin::<C>::new())
+        .add_server_event::<ForceReplicatePredicted<C>>(ChannelKind::Ordered);
+
+        if !app.is_plugin_added::<InputAckPlugin<E>>() {
+            app.add_plugins(InputAckPlugin::<E>::new());
+        }
+
+        if app.world().contains_resource::<RepliconServer>() {
+            app.add_systems(PreUpdate, 
+                correct_predicted_error_system::<C, E>
+                .in_set(ServerBootSet::CorrectReplication)
+            )
+            .add_systems(PostUpdate, 
+                apply_local_predicted_system::<C, E>
+                .in_set(ServerBootSet::ApplyLocalChange)
+            );
+        } else if app.world().contains_resource::<RepliconClient>() {
+            app.add_systems(PreUpdate, (
+                apply_network_predicted_system::<C, E>,
+                handle_correct_predicted::<C, E>
+            ).in_set(ClientBootSet::ApplyReplication))
+            .add_systems(PreUpdate, 
+                rewind_predicted_system::<C, E>
+                .in_set(ClientBootSet::Reconcile)
+                .before(reconcile_system::<E>)
+            )
+            .add_systems(PostUpdate, 
+                cache_predicted_system::<C, E>
+                .in_set(ClientBootSet::Cache)
+            );
+
+            if !app.is_plugin_added::<ReconciliationPlugin<E>>() {
+                app.add_plugins(ReconciliationPlugin::<E>::new());
+            }
+        } else {
+            panic!("could not find replicon server nor client");
+        }
+    }
+}
+
+pub struct MovementModelPlugin<M, E>
+where 
+M: MovementModel<E>,
+E: NetworkMovement {
+    pub model: M,
+    phantom: PhantomData<E>
+}
+
+impl<M, E> MovementModelPlugin<M, E>
+where 
+M: MovementModel<E>,
+E: NetworkMovement {
+    #[inline]
+    pub fn new(model: M) -> Self {
+        Self { 
+            model, 
+            phantom: PhantomData::<E> 
+        }
+    }
+}
+
+impl<M, E> Plugin for MovementModelPlugin<M, E>
+where 
+M: MovementModel<E>,
+E: NetworkMovement {
+    fn build(&self, app: &mut App) {
+        // same model runs as server authority and client prediction
+        app.insert_resource(self.model.clone())
+        .add_systems(FixedUpdate, 
+            movement_model_system::<M, E>
+            .in_set(BootsetMovementModel)
+        );
+
+        if app.world().contains_resource::<RepliconClient>() {
+            app.add_systems(PreUpdate, 
+                replay_movement_system::<M, E>
+                .in_set(ClientBootSet::Reconcile)
+                .after(reconcile_system::<E>)
+            );
+        }
+    }
+}
+
+pub struct InputQueuePlugin<E: NetworkMovement> {
+    pub config: InputQueueConfig,
+    pub dilation_config: TimeDilationConfig,
+    phantom: PhantomData<E>
+}
+
+impl<E: NetworkMovement> InputQueuePlugin<E> {
+    #[inline]
+    pub fn new(
+        config: InputQueueConfig, 
+        dilation_config: TimeDilationConfig
+    ) -> Self {
+        Self { 
+            config,
+            dilation_config, 
+            phantom: PhantomData::<E> 
+        }
+    }
+}
+
+impl<E: NetworkMovement> Plugin for InputQueuePlugin<E> {
+    fn build(&self, app: &mut App) {
+        app.add_server_event::<InputQueueFeedback<E>>(ChannelKind::Unreliable);
+
+        if app.world().contains_resource::<RepliconServer>() {
+            app.insert_resource(self.config.clone())
+            .add_systems(PreUpdate, 
+                insert_input_queue_system::<E>
+                .after(ServerBootSet::UnboxEvent)
+            )
+            .add_systems(FixedPostUpdate, 
+                input_queue_feedback_system::<E>
+            );
+        } else if app.world().contains_resource::<RepliconClient>() {
+            app.insert_resource(self.dilation_config.clone())
+            .insert_resource(TimeDilation::default())
+            .add_systems(PreUpdate, 
+                time_dilation_system::<E>
+                .in_set(ClientBootSet::ApplyReplication)
+            );
         } else {
             panic!("could not find replicon server nor client");
         }
@@ -307,6 +881,8 @@ where A: NetworkAngularVelocity {
 impl<A> Plugin for NetworkAngularVelocityPlugin<A>
 where A: NetworkAngularVelocity {
     fn build(&self, app: &mut App) {
+        app.replicate::<A>();
+
         if app.world().contains_resource::<RepliconServer>() {
             app.add_systems(PostUpdate, 
                 apply_rb_angular_velocity_system::<A>
@@ -323,8 +899,130 @@ where A: NetworkAngularVelocity {
     }
 }
 
+pub struct TranslationExtrapolationPlugin<T, L>
+where 
+T: NetworkTranslation,
+L: NetworkLinearVelocity {
+    pub config: ExtrapolationConfig,
+    phantom: PhantomData<(T, L)>
+}
+
+impl<T, L> TranslationExtrapolationPlugin<T, L>
+where 
+T: NetworkTranslation,
+L: NetworkLinearVelocity {
+    #[inline]
+    pub fn new(config: ExtrapolationConfig) -> Self {
+        Self { 
+            config, 
+            phantom: PhantomData::<(T, L)> 
+        }
+    }
+}
+
+impl<T, L> Plugin for TranslationExtrapolationPlugin<T, L>
+where 
+T: NetworkTranslation,
+L: NetworkLinearVelocity {
+    fn build(&self, app: &mut App) {
+        if app.world().contains_resource::<RepliconClient>() {
+            app.insert_resource(self.config.clone())
+            .add_systems(PreUpdate, (
+                insert_translation_extrapolation_system::<L>
+                .in_set(ClientBootSet::UnboxReplication),
+                extrapolate_translation_system::<T, L>
+                .in_set(ClientBootSet::ApplyReplication)
+                .after(apply_network_translation_system::<T>)
+            ));
+        } else {
+            panic!("could not find replicon client");
+        }
+    }
+}
+
+pub struct RotationExtrapolationPlugin<R, A>
+where 
+R: NetworkRotation,
+A: NetworkAngularVelocity {
+    pub config: ExtrapolationConfig,
+    phantom: PhantomData<(R, A)>
+}
+
+impl<R, A> RotationExtrapolationPlugin<R, A>
+where 
+R: NetworkRotation,
+A: NetworkAngularVelocity {
+    #[inline]
+    pub fn new(config: ExtrapolationConfig) -> Self {
+        Self { 
+            config, 
+            phantom: PhantomData::<(R, A)> 
+        }
+    }
+}
+
+impl<R, A> Plugin for RotationExtrapolationPlugin<R, A>
+where 
+R: NetworkRotation,
+A: NetworkAngularVelocity {
+    fn build(&self, app: &mut App) {
+        if app.world().contains_resource::<RepliconClient>() {
+            app.insert_resource(self.config.clone())
+            .add_systems(PreUpdate, (
+                insert_rotation_extrapolation_system::<A>
+                .in_set(ClientBootSet::UnboxReplication),
+                extrapolate_rotation_system::<R, A>
+                .in_set(ClientBootSet::ApplyReplication)
+                .after(apply_network_rotation_system::<R>)
+            ));
+        } else {
+            panic!("could not find replicon client");
+        }
+    }
+}
+
+pub struct HermiteInterpolationPlugin<T, L>(PhantomData<(T, L)>)
+where 
+T: NetworkTranslation,
+L: NetworkLinearVelocity;
+
+impl<T, L> HermiteInterpolationPlugin<T, L>
+where 
+T: NetworkTranslation,
+L: NetworkLinearVelocity {
+    #[inline]
+    pub fn new() -> Self {
+        Self(PhantomData::<(T, L)>)
+    }
+}
+
+impl<T, L> Plugin for HermiteInterpolationPlugin<T, L>
+where 
+T: NetworkTranslation,
+L: NetworkLinearVelocity {
+    fn build(&self, app: &mut App) {
+        if app.world().contains_resource::<RepliconClient>() {
+            if !app.is_plugin_added::<ComponentCachePlugin<L>>() {
+                app.add_plugins(ComponentCachePlugin::<L>::new());
+            }
+
+            app.add_systems(PreUpdate, 
+                hermite_translation_system::<T, L>
+                .in_set(ClientBootSet::ApplyReplication)
+                .after(apply_network_translation_system::<T>)
+                .before(extrapolate_translation_system::<T, L>)
+            );
+        } else {
+            panic!("could not find replicon client");
+        }
+    }
+}
+
 pub struct ClientEventPlugin<E: NetworkEvent>{
     pub channel_kind: ChannelKind,
+    /// number of latest unacknowledged events sent with each packet, 
+    /// 0 sends events as they are
+    pub redundancy: usize,
     phantom: PhantomData<E>
 }
 
@@ -333,6 +1031,16 @@ impl<E: NetworkEvent> ClientEventPlugin<E> {
     pub fn new(channel_kind: ChannelKind) -> Self {
         Self { 
             channel_kind, 
+            redundancy: 0,
+            phantom: PhantomData::<E> 
+        }
+    }
+
+    #[inline]
+    pub fn redundant(channel_kind: ChannelKind, redundancy: usize) -> Self {
+        Self { 
+            channel_kind, 
+            redundancy,
             phantom: PhantomData::<E> 
         }
     }
@@ -340,8 +1048,14 @@ impl<E: NetworkEvent> ClientEventPlugin<E> {
 
 impl<E: NetworkEvent> Plugin for ClientEventPlugin<E> {
     fn build(&self, app: &mut App) {
-        app.add_plugins(ClientEventCachePlugin::<E>::new())
-        .add_client_event::<E>(self.channel_kind);
+        app.add_plugins(ClientEventCachePlugin::<E>::new());
+
+        if self.redundancy == 0 {
+            app.add_client_event::<E>(self.channel_kind);
+        } else {
+            app.add_client_event::<RedundantEvents<E>>(self.channel_kind)
+            .add_plugins(RedundantEventPlugin::<E>::new(self.redundancy));
+        }
     }
 }
 